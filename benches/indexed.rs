@@ -0,0 +1,47 @@
+//! Compares `astar_path` over a `HashMap` against `astar_path_indexed` over slice storage
+//! on the same grid, run with `cargo bench --bench indexed`
+//!
+
+use std::{
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+use pathfinding_astar::{astar_path, astar_path_indexed, flat_grid_graph, Connectivity};
+
+/// Width and height of the benchmark grid
+const SIZE: usize = 200;
+/// Number of searches timed for each storage
+const RUNS: u32 = 20;
+
+/// Times `RUNS` calls of `f`, giving the mean duration of a call. The lengths of the paths
+/// found are summed and checked so the searches can't be optimised away
+fn time<F: FnMut() -> usize>(mut f: F) -> Duration {
+	let started = Instant::now();
+	let mut steps = 0;
+	for _ in 0..RUNS {
+		steps += f();
+	}
+	let elapsed = started.elapsed();
+	assert!(steps > 0);
+	elapsed / RUNS
+}
+
+fn main() {
+	// weights vary so the search can't head straight for the goal
+	let weights: Vec<f32> = (0..SIZE * SIZE).map(|i| ((i * 7919) % 13) as f32).collect();
+	let nodes: HashMap<usize, (Vec<(usize, f32)>, f32)> =
+		flat_grid_graph(&weights, SIZE, Connectivity::Four);
+	let adjacency: Vec<(Vec<(usize, f32)>, f32)> =
+		(0..nodes.len()).map(|node| nodes[&node].clone()).collect();
+	let end = SIZE * SIZE - 1;
+	assert_eq!(
+		astar_path(0, &nodes, end),
+		astar_path_indexed(0, &adjacency, end)
+	);
+	let hashmap = time(|| astar_path(0, &nodes, end).map_or(0, |path| path.len()));
+	let indexed = time(|| astar_path_indexed(0, &adjacency, end).map_or(0, |path| path.len()));
+	println!("{}x{} grid, mean of {} searches", SIZE, SIZE, RUNS);
+	println!("astar_path         {:?}", hashmap);
+	println!("astar_path_indexed {:?}", indexed);
+}
@@ -0,0 +1,50 @@
+//! Runs [try_astar_path] over arbitrary small graphs, including negative, infinite and `NaN`
+//! distances and weights along with self-loops, to ensure malformed data never panics or
+//! hangs and that any path found travels along edges of the graph
+//!
+//! Run with `cargo +nightly fuzz run astar_path`
+//!
+
+#![no_main]
+
+use std::collections::HashMap;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use pathfinding_astar::try_astar_path;
+
+/// A graph of at most 256 nodes labelled by a `u8`, a node appearing more than once takes
+/// its last definition
+#[derive(Debug, Arbitrary)]
+#[allow(clippy::type_complexity)]
+struct Input {
+	/// Each node label with its `(neighbour_label, distance)` edges and weight
+	nodes: Vec<(u8, Vec<(u8, f32)>, f32)>,
+	/// Label of the node to start from, which may not be in `nodes`
+	start: u8,
+	/// Label of the node to reach, which may not be in `nodes`
+	end: u8,
+}
+
+fuzz_target!(|input: Input| {
+	let nodes: HashMap<u8, (Vec<(u8, f32)>, f32)> = input
+		.nodes
+		.into_iter()
+		.map(|(label, neighbours, weight)| (label, (neighbours, weight)))
+		.collect();
+	if let Ok(Some(path)) = try_astar_path(input.start, &nodes, input.end) {
+		assert_eq!(Some(&input.start), path.first());
+		assert_eq!(Some(&input.end), path.last());
+		for step in path.windows(2) {
+			let connected = nodes[&step[0]]
+				.0
+				.iter()
+				.any(|(neighbour, _)| *neighbour == step[1]);
+			assert!(
+				connected,
+				"{:?} is not a neighbour of {:?}",
+				step[1], step[0]
+			);
+		}
+	}
+});
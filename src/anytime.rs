@@ -0,0 +1,190 @@
+//! Anytime searches which return a quick path up front and refine it towards the optimal
+//! path as time permits
+//!
+
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::{
+	a_star_score, advance_by, check_endpoints, stored_neighbours, stored_weight, Halt, NoListener,
+	SearchState, SearchStats, TieBreak, DEFAULT_SCORE_EPSILON,
+};
+
+/// A search in the style of ARA* which first finds a path with node weights inflated by a
+/// large factor, steering the search greedily towards the goal so a path is found quickly,
+/// then lowers the factor with each call to [AnytimeSearch::improve] until it reaches
+/// `1.0`, at which point the weights are those used by [astar_path](crate::astar_path).
+///
+/// Rather than starting over, each improvement re-scores the routes left queued by the
+/// previous search under the lower inflation and carries on from them, so nodes already
+/// expanded are only explored again where a cheaper route to them turns up. As the distance
+/// traveled to each node only ever falls, each path held travels no further than the last.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path, AnytimeSearch};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 4.0)], 3.0));
+/// nodes.insert(1, (vec![(3, 6.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 2.0));
+/// nodes.insert(3, (vec![], 0.0));
+/// let mut search = AnytimeSearch::new(0, &nodes, 3, 5.0, 2.0);
+/// assert_eq!(Some(&[0, 1, 3][..]), search.path());
+/// while !search.is_optimal() {
+///     assert!(search.improve().is_some());
+/// }
+/// assert_eq!(astar_path(0, &nodes, 3).as_deref(), search.path());
+/// ```
+#[derive(Debug, Clone)]
+#[allow(clippy::type_complexity)]
+pub struct AnytimeSearch<'a, T> {
+	/// The graph being searched
+	nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>,
+	/// Node the path leads to
+	end_node: T,
+	/// Factor applied to each node weight by the most recent search
+	inflation: f32,
+	/// Amount the inflation is lowered by on each improvement
+	step: f32,
+	/// The queue, scores and expanded nodes left by the searches so far, carried on from by
+	/// each improvement
+	state: SearchState<T, ()>,
+	/// The best path found so far and the total distance traveled along it
+	best: Option<(Vec<T>, f32)>,
+}
+
+impl<'a, T> AnytimeSearch<'a, T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	/// Finds an initial path from `start_node` to `end_node` with every node weight multiplied
+	/// by `inflation`, which is lowered by `step` each time the path is improved.
+	///
+	/// An `inflation` below `1.0` is raised to `1.0`, giving the optimal path immediately,
+	/// and a `step` which isn't positive lowers the inflation straight to `1.0` on the first
+	/// improvement.
+	///
+	/// # Panics
+	///
+	/// Under the same conditions as [astar_path](crate::astar_path)
+	#[allow(clippy::type_complexity)]
+	pub fn new(
+		start_node: T,
+		nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>,
+		end_node: T,
+		inflation: f32,
+		step: f32,
+	) -> Self {
+		// ensure nodes data contains start and end points
+		check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+		let inflation = inflation.max(1.0);
+		let state = SearchState::new(
+			start_node,
+			&mut stored_weight(nodes, |_, weight| weight * inflation),
+			&mut |_| (),
+			TieBreak::DistanceTraveled,
+		)
+		.unwrap_or_else(|e| panic!("{}", e));
+		let mut anytime = AnytimeSearch {
+			nodes,
+			end_node,
+			inflation,
+			step,
+			state,
+			best: None,
+		};
+		anytime.best = anytime.search();
+		anytime
+	}
+	/// The factor applied to node weights by the most recent search
+	pub fn inflation(&self) -> f32 {
+		self.inflation
+	}
+	/// Whether the inflation has reached `1.0`, meaning the path can't be improved further
+	pub fn is_optimal(&self) -> bool {
+		self.inflation <= 1.0
+	}
+	/// The best path found so far, `None` if no path exists
+	pub fn path(&self) -> Option<&[T]> {
+		self.best.as_ref().map(|(path, _)| path.as_slice())
+	}
+	/// Lowers the inflation by `step`, to no less than `1.0`, and carries on the search under
+	/// it from where the last one stopped. Once the inflation is `1.0` the path is returned
+	/// without searching again
+	///
+	/// # Panics
+	///
+	/// Under the same conditions as [astar_path](crate::astar_path)
+	#[must_use = "the computed path is discarded if unused"]
+	pub fn improve(&mut self) -> Option<&[T]> {
+		if !self.is_optimal() {
+			self.inflation = if self.step > 0.0 {
+				(self.inflation - self.step).max(1.0)
+			} else {
+				1.0
+			};
+			let inflation = self.inflation;
+			self.state
+				.rescore(
+					stored_weight(self.nodes, |_, weight| weight * inflation),
+					a_star_score,
+				)
+				.unwrap_or_else(|e| panic!("{}", e));
+			self.best = self.search();
+		}
+		self.path()
+	}
+	/// Carries on the search with the current inflation until the end node reaches the head
+	/// of the queue, giving the path to it along with its distance
+	fn search(&mut self) -> Option<(Vec<T>, f32)> {
+		let inflation = self.inflation;
+		let end_node = self.end_node;
+		let halt = advance_by(
+			&mut self.state,
+			|node, _, _| *node == end_node,
+			stored_neighbours(self.nodes),
+			stored_weight(self.nodes, |_, weight| weight * inflation),
+			DEFAULT_SCORE_EPSILON,
+			|_| None,
+			&mut SearchStats::default(),
+			&mut NoListener,
+			|_| (),
+			usize::MAX,
+			f32::INFINITY,
+			a_star_score,
+		)
+		.unwrap_or_else(|e| panic!("{}", e));
+		match halt {
+			Halt::Goal(node, distance) => Some((self.state.path_to(node), distance)),
+			Halt::Ended(_) => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{astar_path, fixtures::large_grid_nodes};
+
+	#[test]
+	/// Improving until the inflation reaches `1.0` never lengthens the path and ends on the
+	/// path given by [astar_path]
+	fn improves_to_astar_path() {
+		let nodes = large_grid_nodes(20);
+		let mut search = AnytimeSearch::new(0, &nodes, 399, 10.0, 3.0);
+		assert!(search.path().is_some());
+		let mut inflations = vec![search.inflation()];
+		let mut distance = search.best.as_ref().unwrap().1;
+		while !search.is_optimal() {
+			assert!(search.improve().is_some());
+			inflations.push(search.inflation());
+			let improved = search.best.as_ref().unwrap().1;
+			assert!(improved <= distance);
+			distance = improved;
+		}
+		assert_eq!(vec![10.0, 7.0, 4.0, 1.0], inflations);
+		assert_eq!(astar_path(0, &nodes, 399).as_deref(), search.path());
+		// further improvements leave the optimal path alone
+		assert_eq!(astar_path(0, &nodes, 399).as_deref(), search.improve());
+	}
+}
@@ -0,0 +1,434 @@
+//! Incremental construction of a graph which is validated as it is built, so that searches
+//! over it can't fail due to malformed data
+//!
+
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+#[cfg(feature = "async")]
+use std::{
+	future::Future,
+	pin::Pin,
+	task::{Context, Poll},
+};
+
+#[cfg(feature = "async")]
+use futures_core::Stream;
+
+#[cfg(feature = "async")]
+use crate::error::StreamError;
+use crate::{
+	error::{AstarError, BuildError},
+	try_astar_path,
+};
+
+/// Assembles a graph one node and edge at a time, checking its structure when
+/// [GraphBuilder::build] is called rather than part way through a search.
+///
+/// ```rust
+/// use pathfinding_astar::GraphBuilder;
+///
+/// let mut builder = GraphBuilder::new();
+/// builder.add_node(0, 3.0).add_node(1, 2.0).add_node(2, 6.0);
+/// builder.add_edge(0, 1, 5.0).add_edge(1, 2, 4.0);
+/// let graph = builder.build().unwrap();
+/// assert_eq!(Ok(Some(vec![0, 1, 2])), graph.path(0, 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraphBuilder<T> {
+	/// Weight of each node added, a node added more than once keeps its first weight
+	weights: HashMap<T, f32>,
+	/// Every node added, in the order they were first added, so problems are reported in
+	/// a consistent order
+	order: Vec<T>,
+	/// Every `(from, to, distance)` edge added, in the order they were added
+	edges: Vec<(T, T, f32)>,
+	/// The first node added again with a different weight, along with both weights
+	conflict: Option<(T, f32, f32)>,
+}
+
+impl<T> Default for GraphBuilder<T> {
+	fn default() -> Self {
+		GraphBuilder {
+			weights: HashMap::new(),
+			order: Vec::new(),
+			edges: Vec::new(),
+			conflict: None,
+		}
+	}
+}
+
+impl<T> GraphBuilder<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	/// A builder without any nodes or edges
+	pub fn new() -> Self {
+		Self::default()
+	}
+	/// Adds a node with the given weight. Adding the same node again with the same weight
+	/// has no effect while a different weight is reported by [GraphBuilder::build]
+	pub fn add_node(&mut self, node: T, weight: f32) -> &mut Self {
+		match self.weights.get(&node) {
+			Some(first) => {
+				if *first != weight && self.conflict.is_none() {
+					self.conflict = Some((node, *first, weight));
+				}
+			}
+			None => {
+				self.weights.insert(node, weight);
+				self.order.push(node);
+			}
+		}
+		self
+	}
+	/// Adds a directional edge, to allow travel both ways add an edge for each direction.
+	/// Both nodes must be added with [GraphBuilder::add_node] before the graph is built
+	pub fn add_edge(&mut self, from: T, to: T, distance: f32) -> &mut Self {
+		self.edges.push((from, to, distance));
+		self
+	}
+	/// Checks the structure of the graph and produces it for searching. Fails with a
+	/// [BuildError] listing the offending edges or nodes if:
+	/// * A node was added more than once with different weights
+	/// * An edge leads to or from a node which was never added
+	/// * A node has a `NaN` weight
+	/// * An edge has a negative or `NaN` distance
+	///
+	/// Problems are reported in that order, only the first kind found is returned. The
+	/// offending edges and nodes are listed in the order they were added
+	pub fn build(&self) -> Result<Graph<T>, BuildError<T>> {
+		if let Some((node, first, second)) = self.conflict {
+			return Err(BuildError::ConflictingWeight {
+				node,
+				first,
+				second,
+			});
+		}
+		let dangling: Vec<(T, T)> = self
+			.edges
+			.iter()
+			.filter(|(from, to, _)| {
+				!self.weights.contains_key(from) || !self.weights.contains_key(to)
+			})
+			.map(|(from, to, _)| (*from, *to))
+			.collect();
+		if !dangling.is_empty() {
+			return Err(BuildError::DanglingEdges(dangling));
+		}
+		let nan_weights: Vec<T> = self
+			.order
+			.iter()
+			.filter(|node| self.weights[*node].is_nan())
+			.copied()
+			.collect();
+		if !nan_weights.is_empty() {
+			return Err(BuildError::NanWeights(nan_weights));
+		}
+		let invalid_distances: Vec<(T, T)> = self
+			.edges
+			.iter()
+			.filter(|(_, _, distance)| distance.is_nan() || *distance < 0.0)
+			.map(|(from, to, _)| (*from, *to))
+			.collect();
+		if !invalid_distances.is_empty() {
+			return Err(BuildError::InvalidDistances(invalid_distances));
+		}
+		let mut nodes: HashMap<T, (Vec<(T, f32)>, f32)> = self
+			.weights
+			.iter()
+			.map(|(node, weight)| (*node, (Vec::new(), *weight)))
+			.collect();
+		for (from, to, distance) in self.edges.iter() {
+			if let Some((neighbours, _)) = nodes.get_mut(from) {
+				neighbours.push((*to, *distance));
+			}
+		}
+		Ok(Graph { nodes })
+	}
+}
+
+#[cfg(feature = "async")]
+impl<T> GraphBuilder<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	/// Builds a graph from every edge produced by `edges` as it arrives, without blocking
+	/// while waiting on the source, such as rows of a database query or a network
+	/// response. Each node an edge touches is given a weight of `0.0`, as with
+	/// [from_edge_list](crate::from_edge_list), use [GraphBuilder::build_from_stream] to
+	/// add weighted nodes beforehand or to load from a source which can fail.
+	///
+	/// Every edge is loaded before the graph is checked as per [GraphBuilder::build], so a
+	/// negative or `NaN` distance is returned as [BuildError::InvalidDistances] once the
+	/// stream has ended.
+	///
+	/// Requires the `async` feature.
+	pub async fn from_stream<S>(edges: S) -> Result<Graph<T>, BuildError<T>>
+	where
+		S: Stream<Item = Edge<T>>,
+	{
+		let mut builder = GraphBuilder::new();
+		let mut edges = Box::pin(edges);
+		while let Some(edge) = (Next {
+			stream: edges.as_mut(),
+		})
+		.await
+		{
+			builder
+				.add_node(edge.from, 0.0)
+				.add_node(edge.to, 0.0)
+				.add_edge(edge.from, edge.to, edge.distance);
+		}
+		builder.build()
+	}
+	/// Adds every edge produced by `edges` as it arrives, without blocking while waiting on
+	/// the source, such as rows of a database query or a network response, and then builds
+	/// the graph as per [GraphBuilder::build]. Nodes are added beforehand with
+	/// [GraphBuilder::add_node].
+	///
+	/// Errors are propagated as soon as they occur: the first `Err` produced by `edges`
+	/// stops loading, leaving the rest of the stream unpolled, and is returned as
+	/// [StreamError::Stream]. Problems with the structure of the graph can only be known once
+	/// every edge has arrived and are returned as [StreamError::Build].
+	///
+	/// Requires the `async` feature.
+	pub async fn build_from_stream<S, E>(mut self, edges: S) -> Result<Graph<T>, StreamError<T, E>>
+	where
+		S: Stream<Item = Result<Edge<T>, E>>,
+	{
+		let mut edges = Box::pin(edges);
+		while let Some(edge) = (Next {
+			stream: edges.as_mut(),
+		})
+		.await
+		{
+			let edge = edge.map_err(StreamError::Stream)?;
+			self.add_edge(edge.from, edge.to, edge.distance);
+		}
+		self.build().map_err(StreamError::Build)
+	}
+}
+
+/// A directional edge received by [GraphBuilder::from_stream] or
+/// [GraphBuilder::build_from_stream]
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edge<T> {
+	/// Node the edge leaves
+	pub from: T,
+	/// Node the edge leads to
+	pub to: T,
+	/// Distance between the two nodes
+	pub distance: f32,
+}
+
+/// Resolves to the next item of `stream`, `None` once it has ended
+#[cfg(feature = "async")]
+struct Next<'a, S> {
+	/// The stream being drawn from
+	stream: Pin<&'a mut S>,
+}
+
+#[cfg(feature = "async")]
+impl<S: Stream> Future for Next<'_, S> {
+	type Output = Option<S::Item>;
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		self.stream.as_mut().poll_next(cx)
+	}
+}
+
+/// A graph produced by [GraphBuilder::build] whose structure has been checked, every
+/// neighbour is a node of the graph and every weight and distance can be scored
+#[derive(Debug, Clone)]
+pub struct Graph<T> {
+	/// The `nodes` data set in the form taken by [astar_path](crate::astar_path)
+	nodes: HashMap<T, (Vec<(T, f32)>, f32)>,
+}
+
+impl<T: Eq + Hash> PartialEq for Graph<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.nodes == other.nodes
+	}
+}
+
+impl<T> Graph<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	/// The `nodes` data set for use with the other pathfinding functions
+	#[allow(clippy::type_complexity)]
+	pub fn nodes(&self) -> &HashMap<T, (Vec<(T, f32)>, f32)> {
+		&self.nodes
+	}
+	/// Takes the `nodes` data set out of the graph
+	#[allow(clippy::type_complexity)]
+	pub fn into_nodes(self) -> HashMap<T, (Vec<(T, f32)>, f32)> {
+		self.nodes
+	}
+	/// Finds the most optimal path from `start_node` to `end_node` as per
+	/// [astar_path](crate::astar_path). As the graph has been checked the only errors
+	/// possible are a `start_node` or `end_node` which isn't part of the graph
+	#[must_use = "the computed path is discarded if unused"]
+	pub fn path(&self, start_node: T, end_node: T) -> Result<Option<Vec<T>>, AstarError<T>> {
+		try_astar_path(start_node, &self.nodes, end_node)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	/// An edge to a node which was never added is reported rather than panicking during
+	/// a later search
+	fn dangling_edge_is_reported() {
+		let mut builder = GraphBuilder::new();
+		builder.add_node(0, 1.0).add_node(1, 1.0);
+		builder.add_edge(0, 1, 1.0).add_edge(1, 2, 1.0);
+		assert_eq!(
+			Err(BuildError::DanglingEdges(vec![(1, 2)])),
+			builder.build()
+		);
+		builder.add_node(2, 1.0);
+		let graph = builder.build().unwrap();
+		assert_eq!(Ok(Some(vec![0, 1, 2])), graph.path(0, 2));
+	}
+	#[test]
+	/// Weights and distances which can't be scored are reported
+	fn invalid_values_are_reported() {
+		let mut builder = GraphBuilder::new();
+		builder.add_node(0, f32::NAN).add_node(1, 1.0);
+		builder.add_edge(0, 1, -1.0);
+		assert_eq!(Err(BuildError::NanWeights(vec![0])), builder.build());
+		let mut builder = GraphBuilder::new();
+		for node in [7, 3, 9, 1, 5, 8] {
+			builder.add_node(node, if node == 1 { 1.0 } else { f32::NAN });
+		}
+		assert_eq!(
+			Err(BuildError::NanWeights(vec![7, 3, 9, 5, 8])),
+			builder.build()
+		);
+		let mut builder = GraphBuilder::new();
+		builder.add_node(0, 1.0).add_node(1, 1.0);
+		builder.add_edge(0, 1, -1.0);
+		assert_eq!(
+			Err(BuildError::InvalidDistances(vec![(0, 1)])),
+			builder.build()
+		);
+	}
+	#[test]
+	/// A node given two different weights is reported, while repeating the same weight is
+	/// harmless
+	fn conflicting_weight_is_reported() {
+		let mut builder = GraphBuilder::new();
+		builder.add_node(0, 1.0).add_node(1, 2.0).add_node(1, 2.0);
+		assert!(builder.build().is_ok());
+		builder.add_node(0, 3.0);
+		assert_eq!(
+			Err(BuildError::ConflictingWeight {
+				node: 0,
+				first: 1.0,
+				second: 3.0
+			}),
+			builder.build()
+		);
+	}
+	/// Yields each of `items` in turn, reporting itself pending before each one as though
+	/// waiting on a slow source
+	#[cfg(feature = "async")]
+	struct SlowStream<I> {
+		/// Items still to be yielded
+		items: std::vec::IntoIter<I>,
+		/// Whether the next poll yields an item rather than reporting pending
+		ready: bool,
+	}
+
+	#[cfg(feature = "async")]
+	impl<I: Unpin> Stream for SlowStream<I> {
+		type Item = I;
+
+		fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I>> {
+			if self.ready {
+				self.ready = false;
+				Poll::Ready(self.items.next())
+			} else {
+				self.ready = true;
+				cx.waker().wake_by_ref();
+				Poll::Pending
+			}
+		}
+	}
+
+	/// Wakes nothing, as [block_on] polls continually
+	#[cfg(feature = "async")]
+	struct NoopWake;
+
+	#[cfg(feature = "async")]
+	impl std::task::Wake for NoopWake {
+		fn wake(self: std::sync::Arc<Self>) {}
+	}
+
+	/// Runs `future` to completion on the current thread, polling it each time it wakes
+	#[cfg(feature = "async")]
+	fn block_on<F: Future>(future: F) -> F::Output {
+		let waker = std::task::Waker::from(std::sync::Arc::new(NoopWake));
+		let mut cx = Context::from_waker(&waker);
+		let mut future = Box::pin(future);
+		loop {
+			if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+				return output;
+			}
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "async")]
+	/// Edges arriving slowly from a stream build a graph which can be searched, while an
+	/// error part way through the stream is returned in place of the graph
+	fn graph_from_edge_stream() {
+		let stream = |edges: Vec<Result<Edge<i32>, String>>| SlowStream {
+			items: edges.into_iter(),
+			ready: false,
+		};
+		let edge = |from, to, distance| Ok(Edge { from, to, distance });
+		let mut builder = GraphBuilder::new();
+		builder.add_node(0, 3.0).add_node(1, 2.0).add_node(2, 6.0);
+		let edges = vec![edge(0, 1, 5.0), edge(1, 2, 4.0), edge(0, 2, 20.0)];
+		let graph = block_on(builder.clone().build_from_stream(stream(edges))).unwrap();
+		assert_eq!(Some(vec![0, 1, 2]), crate::astar_path(0, graph.nodes(), 2));
+		let edges = vec![
+			edge(0, 1, 5.0),
+			Err("connection reset".to_string()),
+			edge(1, 3, 1.0),
+		];
+		assert_eq!(
+			Err(StreamError::Stream("connection reset".to_string())),
+			block_on(builder.clone().build_from_stream(stream(edges)))
+		);
+		let edges = vec![edge(0, 1, 5.0), edge(1, 3, 1.0)];
+		assert_eq!(
+			Err(StreamError::Build(BuildError::DanglingEdges(vec![(1, 3)]))),
+			block_on(builder.build_from_stream(stream(edges)))
+		);
+	}
+	#[test]
+	#[cfg(feature = "async")]
+	/// A stream of bare edges builds a graph of unweighted nodes, with any invalid distance
+	/// reported once the stream has ended
+	fn graph_from_bare_edge_stream() {
+		let stream = |edges: Vec<Edge<i32>>| SlowStream {
+			items: edges.into_iter(),
+			ready: false,
+		};
+		let edge = |from, to, distance| Edge { from, to, distance };
+		let edges = vec![edge(0, 1, 5.0), edge(1, 2, 4.0), edge(0, 2, 20.0)];
+		let graph = block_on(GraphBuilder::from_stream(stream(edges))).unwrap();
+		assert_eq!(Some(vec![0, 1, 2]), crate::astar_path(0, graph.nodes(), 2));
+		assert_eq!(0.0, graph.nodes()[&2].1);
+		let edges = vec![edge(0, 1, 5.0), edge(1, 2, -4.0)];
+		assert_eq!(
+			Err(BuildError::InvalidDistances(vec![(1, 2)])),
+			block_on(GraphBuilder::from_stream(stream(edges)))
+		);
+	}
+}
@@ -0,0 +1,357 @@
+//! Caches of search results which can answer many queries without searching again
+//!
+
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::Debug,
+	hash::Hash,
+};
+
+use crate::{
+	error::AstarError, search, stored_neighbours, stored_weight, SearchStats, DEFAULT_SCORE_EPSILON,
+};
+
+/// The results of searching outward from a fixed start node to every node it can reach,
+/// allowing many queries which share the same start to be answered without searching
+/// again.
+///
+/// The search is the same one performed by [astar_path](crate::astar_path) except that
+/// it continues until every reachable node has been processed. The node each node was
+/// reached from is recorded at the point where a search targeting it would have completed,
+/// forming a shortest-path tree which is walked back from the end node to answer a query.
+/// Every answer matches calling [astar_path](crate::astar_path) directly unless a node
+/// along the way was reached again more cheaply after it was processed, which only happens
+/// where weights overestimate the distance remaining.
+///
+/// The cache doesn't track changes to the graph, if `nodes` is modified build a new cache.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::SourceCache;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let cache = SourceCache::new(0, &nodes);
+/// assert_eq!(Some(vec![0, 1]), cache.path_to(&1));
+/// assert_eq!(Some(vec![0, 1, 2]), cache.path_to(&2));
+/// assert_eq!(Some(9.0), cache.cost_to(&2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SourceCache<T> {
+	/// Node from which every cached path begins
+	start_node: T,
+	/// Each reachable node other than `start_node` mapped to the node it was reached from
+	predecessors: HashMap<T, T>,
+	/// Each reachable node mapped to the distance traveled along its path from `start_node`
+	costs: HashMap<T, f32>,
+}
+
+impl<T> SourceCache<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	/// Searches `nodes` outward from `start_node` and caches the path to every reachable node
+	///
+	/// # Panics
+	///
+	/// If `nodes` doesn't contain `start_node`, a node is referenced as a neighbour but
+	/// is not a key of `nodes`, an edge has a negative distance or a route can't be scored due
+	/// to a `NaN` distance or weight
+	#[allow(clippy::type_complexity)]
+	pub fn new(start_node: T, nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> Self {
+		if !nodes.contains_key(&start_node) {
+			panic!("{}", AstarError::MissingStartNode(start_node));
+		}
+		let mut predecessors: HashMap<T, T> = HashMap::new();
+		let mut costs: HashMap<T, f32> = HashMap::new();
+		search(
+			start_node,
+			|node, route, distance| {
+				// only the first arrival at the head of the queue counts, as it would have
+				// ended a search for the node
+				if !costs.contains_key(node) {
+					costs.insert(*node, distance);
+					if let Some(from) = route.from() {
+						predecessors.insert(*node, from);
+					}
+				}
+				// never stop early so every reachable node is recorded
+				false
+			},
+			stored_neighbours(nodes),
+			stored_weight(nodes, |_, weight| weight),
+			DEFAULT_SCORE_EPSILON,
+			|_| None,
+			&mut SearchStats::default(),
+		)
+		.unwrap_or_else(|e| panic!("{}", e));
+		SourceCache {
+			start_node,
+			predecessors,
+			costs,
+		}
+	}
+	/// The node from which every cached path begins
+	pub fn start_node(&self) -> T {
+		self.start_node
+	}
+	/// The path from the start node to `end_node`, or `None` if it can't be reached
+	#[must_use = "the computed path is discarded if unused"]
+	pub fn path_to(&self, end_node: &T) -> Option<Vec<T>> {
+		if !self.costs.contains_key(end_node) {
+			return None;
+		}
+		// each predecessor reached the head of the queue before the nodes reached from it,
+		// so the walk always ends at the start node which has none
+		let mut path = vec![*end_node];
+		let mut current = *end_node;
+		while let Some(previous) = self.predecessors.get(&current) {
+			path.push(*previous);
+			current = *previous;
+		}
+		path.reverse();
+		Some(path)
+	}
+	/// The distance traveled along the path from the start node to `end_node`, or `None` if
+	/// it can't be reached
+	pub fn cost_to(&self, end_node: &T) -> Option<f32> {
+		self.costs.get(end_node).copied()
+	}
+}
+
+impl<T> SourceCache<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy + Ord,
+{
+	/// Consumes the cache giving every reachable node along with its path and distance, as
+	/// `(node, path, distance)` entries sorted by node label. Unlike iterating a `HashMap`
+	/// the order is the same on every run, suiting serialised snapshots of the tree
+	///
+	/// ```rust
+	/// use std::collections::HashMap;
+	/// use pathfinding_astar::SourceCache;
+	///
+	/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+	/// nodes.insert(0, (vec![(2, 1.0), (1, 5.0)], 3.0));
+	/// nodes.insert(1, (vec![], 2.0));
+	/// nodes.insert(2, (vec![], 6.0));
+	/// let tree = SourceCache::new(0, &nodes).into_sorted_path_tree();
+	/// assert_eq!(
+	///     vec![(0, vec![0], 0.0), (1, vec![0, 1], 5.0), (2, vec![0, 2], 1.0)],
+	///     tree
+	/// );
+	/// ```
+	pub fn into_sorted_path_tree(self) -> Vec<(T, Vec<T>, f32)> {
+		let mut tree: Vec<(T, Vec<T>, f32)> = self
+			.costs
+			.iter()
+			.filter_map(|(node, distance)| Some((*node, self.path_to(node)?, *distance)))
+			.collect();
+		tree.sort_by_key(|(node, _, _)| *node);
+		tree
+	}
+}
+
+/// Finds the distance traveled along the path from `start_node` to each of `goals`, as
+/// given by [astar_handle](crate::astar_handle), from a single search rather than one per
+/// goal. Costs are returned in the same order as `goals`, `None` for a goal which can't be
+/// reached.
+///
+/// Only costs are kept, not paths, and the search stops as soon as every goal has been
+/// reached.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::costs_to_goals;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// nodes.insert(3, (vec![(2, 1.0)], 1.0));
+/// assert_eq!(vec![Some(9.0), None, Some(5.0)], costs_to_goals(0, &nodes, &[2, 3, 1]));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [SourceCache::new]
+#[allow(clippy::type_complexity)]
+pub fn costs_to_goals<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	goals: &[T],
+) -> Vec<Option<f32>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	if !nodes.contains_key(&start_node) {
+		panic!("{}", AstarError::MissingStartNode(start_node));
+	}
+	let mut remaining: HashSet<T> = goals.iter().copied().collect();
+	let mut costs: HashMap<T, f32> = HashMap::with_capacity(remaining.len());
+	search(
+		start_node,
+		|node, _, distance| {
+			if remaining.remove(node) {
+				costs.insert(*node, distance);
+			}
+			remaining.is_empty()
+		},
+		stored_neighbours(nodes),
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e));
+	goals.iter().map(|goal| costs.get(goal).copied()).collect()
+}
+
+/// A [SourceCache] kept from a pursuer's node for chasing a goal which moves every tick.
+///
+/// Queries for the goal's current node are answered from the cached tree and the tree is
+/// only searched again when [Pursuit::move_to] places the pursuer on a different node.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::Pursuit;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let mut pursuit = Pursuit::new(0, &nodes);
+/// assert_eq!(Some(vec![0, 1, 2]), pursuit.path_to(&2));
+/// assert_eq!(Some(vec![0, 1]), pursuit.path_to(&1));
+/// pursuit.move_to(1);
+/// assert_eq!(Some(vec![1, 2]), pursuit.path_to(&2));
+/// ```
+#[derive(Debug, Clone)]
+#[allow(clippy::type_complexity)]
+pub struct Pursuit<'a, T> {
+	/// The graph being searched
+	nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>,
+	/// Paths from the pursuer's current node
+	cache: SourceCache<T>,
+}
+
+impl<'a, T> Pursuit<'a, T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	/// Caches the path from `pursuer` to every node it can reach
+	///
+	/// # Panics
+	///
+	/// Under the same conditions as [SourceCache::new]
+	#[allow(clippy::type_complexity)]
+	pub fn new(pursuer: T, nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>) -> Self {
+		Pursuit {
+			nodes,
+			cache: SourceCache::new(pursuer, nodes),
+		}
+	}
+	/// The node the pursuer currently occupies
+	pub fn pursuer(&self) -> T {
+		self.cache.start_node()
+	}
+	/// Places the pursuer on `node`, searching again only if it differs from the node the
+	/// pursuer already occupies. Returns whether the tree was recomputed
+	///
+	/// # Panics
+	///
+	/// Under the same conditions as [SourceCache::new]
+	pub fn move_to(&mut self, node: T) -> bool {
+		if node == self.pursuer() {
+			return false;
+		}
+		self.cache = SourceCache::new(node, self.nodes);
+		true
+	}
+	/// The path from the pursuer to the goal's current node, or `None` if it can't be reached
+	#[must_use = "the computed path is discarded if unused"]
+	pub fn path_to(&self, goal: &T) -> Option<Vec<T>> {
+		self.cache.path_to(goal)
+	}
+	/// The distance traveled along the path from the pursuer to the goal's current node, or
+	/// `None` if it can't be reached
+	pub fn cost_to(&self, goal: &T) -> Option<f32> {
+		self.cache.cost_to(goal)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fixtures::{grid_nodes, no_path_nodes};
+	use crate::{astar_handle, astar_path};
+
+	#[test]
+	/// Paths answered by the cache match searching the grid directly
+	fn cache_matches_astar_path() {
+		let nodes = grid_nodes();
+		let cache = SourceCache::new(0, &nodes);
+		assert_eq!(astar_path(0, &nodes, 11), cache.path_to(&11));
+		assert_eq!(astar_path(0, &nodes, 15), cache.path_to(&15));
+		for end in nodes.keys() {
+			assert_eq!(astar_path(0, &nodes, *end), cache.path_to(end));
+		}
+	}
+	#[test]
+	/// Unreachable nodes have no cached path
+	fn cache_unreachable() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 1.0));
+		nodes.insert(1, (vec![], 1.0));
+		nodes.insert(2, (vec![(0, 1.0)], 1.0));
+		let cache = SourceCache::new(0, &nodes);
+		assert_eq!(None, cache.path_to(&2));
+		assert_eq!(Some(vec![0]), cache.path_to(&0));
+		assert_eq!(Some(0.0), cache.cost_to(&0));
+	}
+	#[test]
+	/// A goal moving across three nodes is answered as [astar_path] from the fixed pursuer,
+	/// and the tree is only recomputed once the pursuer moves
+	fn pursuit_of_moving_goal() {
+		let nodes = grid_nodes();
+		let mut pursuit = Pursuit::new(0, &nodes);
+		for goal in [11, 14, 15] {
+			assert_eq!(astar_path(0, &nodes, goal), pursuit.path_to(&goal));
+		}
+		assert!(!pursuit.move_to(0));
+		assert!(pursuit.move_to(4));
+		assert_eq!(4, pursuit.pursuer());
+		assert_eq!(astar_path(4, &nodes, 15), pursuit.path_to(&15));
+	}
+	#[test]
+	/// Costs to several grid nodes from one search match the cost of each path found
+	/// separately
+	fn costs_to_grid_goals() {
+		let nodes = grid_nodes();
+		let goals = [3, 11, 15];
+		let expected: Vec<Option<f32>> = goals
+			.iter()
+			.map(|goal| astar_handle(0, &nodes, *goal).map(|handle| handle.cost()))
+			.collect();
+		assert_eq!(expected, costs_to_goals(0, &nodes, &goals));
+		assert_eq!(
+			vec![Some(6.0), Some(0.0)],
+			costs_to_goals(0, &nodes, &[15, 0])
+		);
+		assert_eq!(vec![None], costs_to_goals(0, &no_path_nodes(), &[5]));
+	}
+	#[test]
+	/// Separately built trees of the grid serialise identically once sorted
+	fn sorted_path_tree_is_stable() {
+		let nodes = grid_nodes();
+		let first = format!("{:?}", SourceCache::new(0, &nodes).into_sorted_path_tree());
+		let second = format!("{:?}", SourceCache::new(0, &nodes).into_sorted_path_tree());
+		assert_eq!(first.as_bytes(), second.as_bytes());
+		let tree = SourceCache::new(0, &nodes).into_sorted_path_tree();
+		let labels: Vec<i32> = tree.iter().map(|(node, _, _)| *node).collect();
+		assert_eq!((0..16).collect::<Vec<i32>>(), labels);
+		assert_eq!((15, vec![0, 4, 8, 9, 10, 11, 15], 6.0), tree[15]);
+	}
+}
@@ -0,0 +1,277 @@
+//! Shrinking a `nodes` data set ahead of searching it by collapsing corridors of nodes
+//! which offer no choice of direction
+//!
+
+use std::{
+	collections::{HashMap, HashSet},
+	hash::Hash,
+};
+
+use crate::graph::build_reverse;
+
+/// Records the nodes collapsed by [contract_chains] so that a path found over the
+/// contracted graph can be expanded back into every node it passes through.
+///
+/// ```rust
+/// use pathfinding_astar::{astar_path, contract_chains, from_edge_list};
+///
+/// let edges = [
+///     (0, 1, 1.0), (1, 0, 1.0), (0, 4, 1.0), (4, 0, 1.0), (0, 5, 1.0), (5, 0, 1.0),
+///     (1, 2, 1.0), (2, 1, 1.0), (2, 3, 1.0), (3, 2, 1.0),
+/// ];
+/// let nodes = from_edge_list(&edges, &[]);
+/// let (contracted, chains) = contract_chains(&nodes);
+/// assert!(chains.is_contracted(&2));
+/// let path = astar_path(0, &contracted, 3).unwrap();
+/// assert_eq!(vec![0, 1, 2, 3], chains.expand(&path));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChainMap<T: Eq + Hash> {
+	/// The nodes collapsed into the edge from the first node of the key to the second, in
+	/// the order they are passed through
+	chains: HashMap<(T, T), Vec<T>>,
+	/// Every node collapsed into an edge
+	contracted: HashSet<T>,
+}
+
+impl<T> ChainMap<T>
+where
+	T: Eq + Hash + Copy,
+{
+	/// Whether `node` was collapsed into an edge and so is missing from the contracted
+	/// graph, in which case it can't be used as the start or end of a search over it
+	pub fn is_contracted(&self, node: &T) -> bool {
+		self.contracted.contains(node)
+	}
+	/// The nodes collapsed into the edge from `from` to `to`, in the order they are passed
+	/// through, `None` if the edge isn't a contracted chain
+	pub fn chain(&self, from: &T, to: &T) -> Option<&[T]> {
+		self.chains.get(&(*from, *to)).map(|chain| chain.as_slice())
+	}
+	/// Expands a `path` found over the contracted graph into the path over the original
+	/// graph, restoring the nodes collapsed into each edge it travels along
+	#[must_use = "the computed path is discarded if unused"]
+	pub fn expand(&self, path: &[T]) -> Vec<T> {
+		let mut expanded = Vec::with_capacity(path.len());
+		for step in path.windows(2) {
+			expanded.push(step[0]);
+			if let Some(chain) = self.chains.get(&(step[0], step[1])) {
+				expanded.extend(chain.iter().copied());
+			}
+		}
+		expanded.extend(path.last().copied());
+		expanded
+	}
+}
+
+/// Collapses every maximal chain of degree-2 nodes in `nodes` into a single edge in each
+/// direction between the nodes at either end of it, so a search travels along a long
+/// corridor in one expansion rather than one per node. The returned [ChainMap] expands a
+/// path over the contracted graph back into the full list of nodes.
+///
+/// A node is part of a chain when it has exactly two neighbours, distinct and other than
+/// itself, and they are the only nodes with an edge into it. The edge replacing a chain
+/// takes the total distance along it, so the cost of every route is preserved, while the
+/// weights of the collapsed nodes are dropped. As weights guide the search, a path over
+/// the contracted graph may differ from one over `nodes` where they were steering it.
+///
+/// A chain is left in place where it would loop back to the node it leaves, or where its
+/// ends are already joined by an edge or another chain, as the replacing edge couldn't be
+/// told apart from the existing one. Rings made up entirely of degree-2 nodes are also
+/// left in place. Start and end nodes of later searches must not be collapsed, see
+/// [ChainMap::is_contracted].
+///
+/// ```rust
+/// use pathfinding_astar::{contract_chains, from_edge_list};
+///
+/// let edges = [
+///     (0, 1, 1.0), (1, 0, 1.0), (1, 2, 2.0), (2, 1, 2.0),
+///     (2, 3, 3.0), (3, 2, 3.0), (3, 4, 1.0), (4, 3, 1.0), (3, 5, 1.0), (5, 3, 1.0),
+/// ];
+/// let nodes = from_edge_list(&edges, &[]);
+/// let (contracted, chains) = contract_chains(&nodes);
+/// assert_eq!(Some(&(vec![(3, 6.0)], 0.0)), contracted.get(&0));
+/// assert_eq!(Some(&[1, 2][..]), chains.chain(&0, &3));
+/// assert_eq!(Some(&[2, 1][..]), chains.chain(&3, &0));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn contract_chains<T>(
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> (HashMap<T, (Vec<(T, f32)>, f32)>, ChainMap<T>)
+where
+	T: Eq + Hash + Copy,
+{
+	let reverse = build_reverse(nodes);
+	let is_link = |node: &T| -> bool {
+		let neighbours = match nodes.get(node) {
+			Some(x) => &x.0,
+			None => return false,
+		};
+		if neighbours.len() != 2 {
+			return false;
+		}
+		let (a, b) = (neighbours[0].0, neighbours[1].0);
+		if a == b || a == *node || b == *node || !nodes.contains_key(&a) || !nodes.contains_key(&b)
+		{
+			return false;
+		}
+		match reverse.get(node) {
+			Some(into) => {
+				into.len() == 2
+					&& ((into[0].0 == a && into[1].0 == b) || (into[0].0 == b && into[1].0 == a))
+			}
+			None => false,
+		}
+	};
+	// walk from each end of every chain to the node at its other end, recording the
+	// distance travelled and the nodes passed through
+	let mut walks: Vec<(T, T, T, f32, Vec<T>)> = Vec::new();
+	for (from, (neighbours, _)) in nodes.iter() {
+		if is_link(from) {
+			continue;
+		}
+		for (first, distance) in neighbours.iter() {
+			if !is_link(first) {
+				continue;
+			}
+			let (mut previous, mut current) = (*from, *first);
+			let mut total = *distance;
+			let mut chain = vec![current];
+			loop {
+				let (next, step) = nodes[&current]
+					.0
+					.iter()
+					.copied()
+					.find(|(neighbour, _)| *neighbour != previous)
+					.expect("a link has two distinct neighbours");
+				total += step;
+				if !is_link(&next) {
+					walks.push((*from, *first, next, total, chain));
+					break;
+				}
+				previous = current;
+				current = next;
+				chain.push(current);
+			}
+		}
+	}
+	let mut joining: HashMap<(T, T), usize> = HashMap::new();
+	for (from, _, to, _, _) in walks.iter() {
+		*joining.entry((*from, *to)).or_default() += 1;
+	}
+	let has_edge = |from: &T, to: &T| nodes[from].0.iter().any(|(n, _)| n == to);
+	// the replacing edge for the first node of each collapsed chain, keyed by the node the
+	// chain is entered from
+	let mut shortcuts: HashMap<(T, T), (T, f32)> = HashMap::new();
+	let mut chain_map = ChainMap {
+		chains: HashMap::new(),
+		contracted: HashSet::new(),
+	};
+	for (from, first, to, total, chain) in walks.into_iter() {
+		if from == to || joining[&(from, to)] > 1 || has_edge(&from, &to) || has_edge(&to, &from) {
+			continue;
+		}
+		shortcuts.insert((from, first), (to, total));
+		chain_map.contracted.extend(chain.iter().copied());
+		chain_map.chains.insert((from, to), chain);
+	}
+	let contracted = nodes
+		.iter()
+		.filter(|(node, _)| !chain_map.contracted.contains(node))
+		.map(|(node, (neighbours, weight))| {
+			let neighbours = neighbours
+				.iter()
+				.map(|(neighbour, distance)| {
+					shortcuts
+						.get(&(*node, *neighbour))
+						.copied()
+						.unwrap_or((*neighbour, *distance))
+				})
+				.collect();
+			(*node, (neighbours, *weight))
+		})
+		.collect();
+	(contracted, chain_map)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{astar_path, astar_path_with_stats, from_edge_list};
+
+	/// Two 3x3 rooms, labelled `0..9` and `200..209` row by row, joined by a corridor of
+	/// twenty nodes `100..120` running from the corner `8` of one to the corner `200` of
+	/// the other. Distances differ slightly across each room so no two routes tie
+	#[allow(clippy::type_complexity)]
+	fn corridor_map() -> HashMap<i32, (Vec<(i32, f32)>, f32)> {
+		let mut edges: Vec<(i32, i32, f32)> = Vec::new();
+		let mut join = |a: i32, b: i32, distance: f32| {
+			edges.push((a, b, distance));
+			edges.push((b, a, distance));
+		};
+		for base in [0, 200].iter() {
+			for y in 0..3 {
+				for x in 0..3 {
+					let node = base + x + y * 3;
+					let distance = 1.0 + ((x * 3 + y * 5) % 4) as f32 * 0.1;
+					if x < 2 {
+						join(node, node + 1, distance);
+					}
+					if y < 2 {
+						join(node, node + 3, distance + 0.05);
+					}
+				}
+			}
+		}
+		join(8, 100, 1.0);
+		for node in 100..119 {
+			join(node, node + 1, 1.0);
+		}
+		join(119, 200, 1.0);
+		from_edge_list(&edges, &[])
+	}
+
+	#[test]
+	/// Searching the contracted map travels the corridor in a single expansion yet expands
+	/// back to the same path as searching the full map
+	fn contracted_corridor_same_path_fewer_expansions() {
+		let nodes = corridor_map();
+		let (contracted, chains) = contract_chains(&nodes);
+		assert!((100..120).all(|node| chains.is_contracted(&node)));
+		assert!(!chains.is_contracted(&4) && !chains.is_contracted(&204));
+		let (path, stats) = astar_path_with_stats(4, &nodes, 204);
+		let (contracted_path, contracted_stats) = astar_path_with_stats(4, &contracted, 204);
+		assert_eq!(path, contracted_path.map(|path| chains.expand(&path)));
+		assert!(path.unwrap().len() > 20);
+		assert!(contracted_stats.expansions + 20 <= stats.expansions);
+	}
+	#[test]
+	/// Chains looping back to where they start, doubling up an existing edge or forming a
+	/// ring are left in place
+	fn ambiguous_chains_are_kept() {
+		let mut edges: Vec<(i32, i32, f32)> = Vec::new();
+		let mut join = |a: i32, b: i32| {
+			edges.push((a, b, 1.0));
+			edges.push((b, a, 1.0));
+		};
+		// a loop of 1 and 2 leaving and returning to 0, which also leads to 3 and 4
+		join(0, 1);
+		join(1, 2);
+		join(2, 0);
+		join(0, 3);
+		join(0, 4);
+		// 5 bridges 3 and 4 which are already joined directly
+		join(3, 5);
+		join(5, 4);
+		join(3, 4);
+		// a ring of nothing but links
+		join(10, 11);
+		join(11, 12);
+		join(12, 10);
+		let nodes = from_edge_list(&edges, &[]);
+		let (contracted, chains) = contract_chains(&nodes);
+		assert_eq!(nodes, contracted);
+		assert_eq!(ChainMap::default(), chains);
+		assert_eq!(Some(vec![1, 0, 4]), astar_path(1, &contracted, 4));
+	}
+}
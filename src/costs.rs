@@ -0,0 +1,889 @@
+//! Queries built on the shortest distances from a single source node to every node it
+//! can reach
+//!
+
+use std::{
+	cmp::Ordering,
+	collections::{BinaryHeap, HashMap, HashSet},
+	fmt::Debug,
+	hash::Hash,
+};
+
+use crate::{
+	astar_path, check_endpoints,
+	error::AstarError,
+	graph::{build_reverse, edge_distance},
+	no_worse_than, DEFAULT_SCORE_EPSILON,
+};
+
+/// A cost per node, such as the shortest distance to reach it, its weight or a score
+/// assigned to it. Functions which produce or accept a value for each node use this alias
+/// so buffers can be annotated consistently.
+///
+/// ```rust
+/// use pathfinding_astar::CostMap;
+///
+/// let mut costs: CostMap<(i32, i32)> = CostMap::new();
+/// costs.insert((0, 0), 0.0);
+/// costs.insert((0, 1), 1.5);
+/// assert_eq!(Some(&1.5), costs.get(&(0, 1)));
+/// ```
+pub type CostMap<T> = HashMap<T, f32>;
+
+/// Pairs a node with the distance traveled to reach it, ordered such that a
+/// [BinaryHeap] pops the shortest distance first
+pub(crate) struct MinDistance<T> {
+	/// Total distance traveled from the source to `node`
+	pub(crate) distance: f32,
+	/// Label of the node reached
+	pub(crate) node: T,
+}
+
+impl<T> PartialEq for MinDistance<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl<T> Eq for MinDistance<T> {}
+
+impl<T> PartialOrd for MinDistance<T> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<T> Ord for MinDistance<T> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// reversed so the max-heap yields the smallest distance
+		other
+			.distance
+			.partial_cmp(&self.distance)
+			.unwrap_or(Ordering::Equal)
+	}
+}
+
+/// Computes the shortest distance from `source` to every node it can reach, ignoring node
+/// weights. Nodes referenced as a neighbour but missing from `nodes` are treated as having
+/// no neighbours of their own
+#[allow(clippy::type_complexity)]
+pub(crate) fn shortest_distances<T>(
+	source: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> CostMap<T>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	shortest_distances_from_any(&[source], nodes)
+}
+
+/// Computes the shortest distance from the nearest of `sources` to every node reachable
+/// from any of them, as per [shortest_distances]
+#[allow(clippy::type_complexity)]
+pub(crate) fn shortest_distances_from_any<T>(
+	sources: &[T],
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> CostMap<T>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	shortest_distances_within(sources, nodes, f32::INFINITY)
+}
+
+/// Computes the shortest distance from the nearest of `sources` to every node reachable
+/// from any of them without traveling further than `max_distance`, as per [shortest_distances]
+#[allow(clippy::type_complexity)]
+fn shortest_distances_within<T>(
+	sources: &[T],
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	max_distance: f32,
+) -> CostMap<T>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	let mut distances: CostMap<T> = CostMap::new();
+	let mut heap = BinaryHeap::new();
+	for source in sources.iter() {
+		distances.insert(*source, 0.0);
+		heap.push(MinDistance {
+			distance: 0.0,
+			node: *source,
+		});
+	}
+	while let Some(MinDistance { distance, node }) = heap.pop() {
+		// skip stale entries superseded by a shorter route
+		if distance > distances[&node] {
+			continue;
+		}
+		let neighbours = match nodes.get(&node) {
+			Some(x) => &x.0,
+			None => continue,
+		};
+		for (neighbour, edge) in neighbours.iter() {
+			let distance_traveled = distance + edge;
+			if distance_traveled > max_distance {
+				continue;
+			}
+			let improved = match distances.get(neighbour) {
+				Some(existing) => distance_traveled < *existing,
+				None => true,
+			};
+			if improved {
+				distances.insert(*neighbour, distance_traveled);
+				heap.push(MinDistance {
+					distance: distance_traveled,
+					node: *neighbour,
+				});
+			}
+		}
+	}
+	distances
+}
+
+/// Finds every node which can be reached from `start_node` without the total distance
+/// traveled exceeding `max_cost`, mapped to the lowest distance needed to reach it. Node
+/// weights are ignored and `start_node` is always included at a cost of `0.0`.
+///
+/// Useful for highlighting the tiles a unit can move to with a limited number of movement
+/// points.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::reachable_within_cost;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 2.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 2.0)], 1.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// let reachable = reachable_within_cost(0, &nodes, 3.0);
+/// assert_eq!(2, reachable.len());
+/// assert_eq!(Some(&2.0), reachable.get(&1));
+/// ```
+///
+/// # Panics
+///
+/// If `nodes` doesn't contain `start_node`
+#[allow(clippy::type_complexity)]
+pub fn reachable_within_cost<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	max_cost: f32,
+) -> CostMap<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	if !nodes.contains_key(&start_node) {
+		panic!("{}", AstarError::MissingStartNode(start_node));
+	}
+	shortest_distances_within(&[start_node], nodes, max_cost)
+}
+
+/// Finds the `k` nodes reachable from `start_node` with the lowest shortest distance from
+/// it, excluding `start_node` itself, sorted nearest first along with their distances.
+/// Node weights are ignored and fewer than `k` are returned when fewer can be reached.
+///
+/// The search outward from `start_node` stops once `k` nodes have been settled, so only
+/// the neighbourhood of `start_node` is explored, useful for queries such as the closest
+/// few resource nodes. Where nodes tie on distance which of them is returned first is
+/// unspecified.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::k_nearest;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 3.0), (2, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 2.0)], 1.0));
+/// nodes.insert(2, (vec![(1, 1.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// assert_eq!(vec![(2, 1.0), (1, 2.0)], k_nearest(0, &nodes, 2));
+/// assert_eq!(3, k_nearest(0, &nodes, 10).len());
+/// ```
+///
+/// # Panics
+///
+/// If `nodes` doesn't contain `start_node`
+#[allow(clippy::type_complexity)]
+pub fn k_nearest<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	k: usize,
+) -> Vec<(T, f32)>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	if !nodes.contains_key(&start_node) {
+		panic!("{}", AstarError::MissingStartNode(start_node));
+	}
+	let mut nearest = Vec::with_capacity(k);
+	let mut distances: CostMap<T> = CostMap::new();
+	let mut settled: HashSet<T> = HashSet::new();
+	let mut heap = BinaryHeap::new();
+	distances.insert(start_node, 0.0);
+	heap.push(MinDistance {
+		distance: 0.0,
+		node: start_node,
+	});
+	while nearest.len() < k {
+		let MinDistance { distance, node } = match heap.pop() {
+			Some(x) => x,
+			None => break,
+		};
+		// skip stale entries superseded by a shorter route
+		if !settled.insert(node) {
+			continue;
+		}
+		if node != start_node {
+			nearest.push((node, distance));
+		}
+		let neighbours = match nodes.get(&node) {
+			Some(x) => &x.0,
+			None => continue,
+		};
+		for (neighbour, edge) in neighbours.iter() {
+			let distance_traveled = distance + edge;
+			let improved = match distances.get(neighbour) {
+				Some(existing) => distance_traveled < *existing,
+				None => true,
+			};
+			if improved {
+				distances.insert(*neighbour, distance_traveled);
+				heap.push(MinDistance {
+					distance: distance_traveled,
+					node: *neighbour,
+				});
+			}
+		}
+	}
+	nearest
+}
+
+/// Whether a directed path leads from `a` to `b` and another leads back from `b` to `a`.
+/// On a directed graph one may exist without the other, such as along a one-way street.
+/// A node is always reachable from itself, nodes missing from `nodes` reach nothing.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::mutually_reachable;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(0, 1.0), (2, 1.0)], 1.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// assert!(mutually_reachable(0, 1, &nodes));
+/// assert!(!mutually_reachable(0, 2, &nodes));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn mutually_reachable<T>(a: T, b: T, nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> bool
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let reaches = |from: T, to: T| {
+		nodes.contains_key(&from) && shortest_distances(from, nodes).contains_key(&to)
+	};
+	reaches(a, b) && reaches(b, a)
+}
+
+/// Finds the lowest total distance of any path from `start_node` to `end_node`, `None` if
+/// no path exists.
+///
+/// Where many paths share the lowest cost, which of them [astar_path](crate::astar_path)
+/// returns depends on how ties are broken, and as weights are free to overestimate the
+/// distance remaining the path it returns may cost more than the best. The cost here is
+/// instead read from the shortest distance settled for `end_node` by a search ignoring
+/// weights, so it is the optimal cost however ties fall and is the same from run to run.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_optimal_cost;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 2.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 2.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// assert_eq!(Some(3.0), astar_optimal_cost(0, &nodes, 3));
+/// assert_eq!(None, astar_optimal_cost(3, &nodes, 0));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[allow(clippy::type_complexity)]
+pub fn astar_optimal_cost<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<f32>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	shortest_distances(start_node, nodes)
+		.get(&end_node)
+		.copied()
+}
+
+/// Searches for a path between each `(start, end)` pair of `pairs` and returns the index
+/// of the pair whose path costs the least, along with that path and its cost, such as for
+/// picking the cheapest of several delivery routes. Pairs with no route are skipped and
+/// `None` is returned if none has a route.
+///
+/// Pairs are ranked by the shortest distance between their nodes as per
+/// [astar_optimal_cost] and the path of the cheapest is found by
+/// [astar_path](crate::astar_path). Where several pairs share the lowest cost the earliest
+/// is returned.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::cheapest_of;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 4.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 1.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// assert_eq!(Some((1, vec![1, 2], 1.0)), cheapest_of(&[(0, 2), (1, 2), (2, 0)], &nodes));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path) for any of the pairs
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn cheapest_of<T>(
+	pairs: &[(T, T)],
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> Option<(usize, Vec<T>, f32)>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let mut cheapest: Option<(usize, Vec<T>, f32)> = None;
+	for (index, (start_node, end_node)) in pairs.iter().enumerate() {
+		let cost = match astar_optimal_cost(*start_node, nodes, *end_node) {
+			Some(x) => x,
+			None => continue,
+		};
+		if cheapest.as_ref().map_or(true, |(_, _, best)| cost < *best) {
+			if let Some(path) = astar_path(*start_node, nodes, *end_node) {
+				cheapest = Some((index, path, cost));
+			}
+		}
+	}
+	cheapest
+}
+
+/// Finds the node reachable from `start_node` which is furthest from it, i.e. the node
+/// with the greatest shortest distance, along with that distance. Node weights are
+/// ignored and nodes which can't be reached are disregarded.
+///
+/// Useful for map analysis such as finding the most remote tile. Where several nodes share
+/// the greatest distance any one of them may be returned.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::farthest_node;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 3.0), (2, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 2.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// assert_eq!(Some((1, 3.0)), farthest_node(0, &nodes));
+/// ```
+///
+/// Returns `None` if `nodes` doesn't contain `start_node`
+#[allow(clippy::type_complexity)]
+pub fn farthest_node<T>(start_node: T, nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> Option<(T, f32)>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	if !nodes.contains_key(&start_node) {
+		return None;
+	}
+	shortest_distances(start_node, nodes)
+		.into_iter()
+		.fold(None, |farthest, (node, distance)| match farthest {
+			Some((_, max)) if max >= distance => farthest,
+			_ => Some((node, distance)),
+		})
+}
+
+/// Finds the diameter of the component reachable from `start_node`, the greatest shortest
+/// distance between any two nodes of it. Node weights are ignored.
+///
+/// Each node reachable from `start_node` is searched outward from in turn, so the cost is
+/// one full single source search per node of the component, `O(V)` searches in all. Useful
+/// for validating generated maps where the longest traversal needs to be kept short.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::component_diameter;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 2.0)], 1.0));
+/// nodes.insert(1, (vec![(0, 2.0), (2, 5.0)], 1.0));
+/// nodes.insert(2, (vec![(1, 5.0)], 1.0));
+/// nodes.insert(3, (vec![(0, 40.0)], 1.0));
+/// assert_eq!(Some(7.0), component_diameter(0, &nodes));
+/// ```
+///
+/// Returns `None` if `nodes` doesn't contain `start_node`
+#[allow(clippy::type_complexity)]
+pub fn component_diameter<T>(start_node: T, nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> Option<f32>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	if !nodes.contains_key(&start_node) {
+		return None;
+	}
+	// any node reachable from a member of the component is itself a member, so each search
+	// stays within the component
+	let diameter = shortest_distances(start_node, nodes)
+		.keys()
+		.filter_map(|node| farthest_node(*node, nodes))
+		.fold(0.0, |diameter: f32, (_, distance)| diameter.max(distance));
+	Some(diameter)
+}
+
+/// Computes, in one call, the shortest distance from `center` to every node it can reach
+/// along with the shortest distance to `center` from every node which can reach it, as
+/// `(from_center, to_center)`. Node weights are ignored.
+///
+/// The distances to `center` are found by searching outward over the reversed edges, see
+/// [build_reverse](crate::build_reverse). Useful for betweenness style metrics where
+/// a node lies on a shortest route through `center` when its two distances sum to the
+/// length of that route.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::both_trees;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(0, 3.0)], 1.0));
+/// let (from_center, to_center) = both_trees(0, &nodes);
+/// assert_eq!(Some(&1.0), from_center.get(&1));
+/// assert_eq!(Some(&3.0), to_center.get(&1));
+/// ```
+///
+/// # Panics
+///
+/// If `nodes` doesn't contain `center`
+#[allow(clippy::type_complexity)]
+pub fn both_trees<T>(
+	center: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> (CostMap<T>, CostMap<T>)
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	if !nodes.contains_key(&center) {
+		panic!("{}", AstarError::MissingStartNode(center));
+	}
+	(
+		shortest_distances(center, nodes),
+		shortest_distances(center, &reversed_nodes(nodes)),
+	)
+}
+
+/// The `nodes` data set with every edge turned around and weights of zero, so the shortest
+/// distances from a node over it are the shortest distances to that node over `nodes`
+#[allow(clippy::type_complexity)]
+fn reversed_nodes<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> HashMap<T, (Vec<(T, f32)>, f32)>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	build_reverse(nodes)
+		.into_iter()
+		.map(|(node, edges)| (node, (edges, 0.0)))
+		.collect()
+}
+
+/// Checks whether a previously computed `path` is still a shortest route through `nodes`,
+/// such as after edges have been edited, without a full recompute.
+///
+/// The path must still follow edges of `nodes` and no route from its first node to its
+/// last may be shorter in total distance, which in turn means every prefix of the path is
+/// a shortest route to the node it ends on. Node weights are ignored. The search for a
+/// shorter route is bounded by the distance of `path` itself so only the local area
+/// around it is explored. An empty path is never optimal.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::is_path_still_optimal;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 5.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 1.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// assert!(is_path_still_optimal(&[0, 1, 2], &nodes));
+/// // a shortcut appears
+/// nodes.insert(0, (vec![(1, 1.0), (2, 0.5)], 1.0));
+/// assert!(!is_path_still_optimal(&[0, 1, 2], &nodes));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn is_path_still_optimal<T>(path: &[T], nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> bool
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let (first, last) = match (path.first(), path.last()) {
+		(Some(first), Some(last)) => (first, last),
+		_ => return false,
+	};
+	if !path.iter().all(|node| nodes.contains_key(node)) {
+		return false;
+	}
+	let mut path_distance = 0.0;
+	for step in path.windows(2) {
+		match edge_distance(nodes, &step[0], &step[1]) {
+			Some(distance) => path_distance += distance,
+			// an edge along the path has been removed
+			None => return false,
+		}
+	}
+	let distances =
+		shortest_distances_within(&[*first], nodes, path_distance + DEFAULT_SCORE_EPSILON);
+	match distances.get(last) {
+		Some(shortest) => path_distance <= shortest + DEFAULT_SCORE_EPSILON,
+		None => false,
+	}
+}
+
+/// Measures how unevenly `path` steps between nodes, as the sum of the squared differences
+/// between the distances of each pair of consecutive edges. A path whose every step has
+/// the same distance scores `0.0`, the score growing with sudden long jumps or short hops,
+/// which suits ranking candidate paths for smooth movement. This is the penalty applied by
+/// [astar_path_smooth](crate::astar_path_smooth).
+///
+/// Returns `None` if two consecutive nodes of `path` aren't joined by an edge of `nodes`.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::path_smoothness;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 3.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 3.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// assert_eq!(Some(0.0), path_smoothness(&[1, 2, 3], &nodes));
+/// assert_eq!(Some(4.0), path_smoothness(&[0, 1, 2, 3], &nodes));
+/// assert_eq!(None, path_smoothness(&[0, 2], &nodes));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn path_smoothness<T>(path: &[T], nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> Option<f32>
+where
+	T: Eq + Hash,
+{
+	let distances: Option<Vec<f32>> = path
+		.windows(2)
+		.map(|step| edge_distance(nodes, &step[0], &step[1]))
+		.collect();
+	Some(
+		distances?
+			.windows(2)
+			.map(|pair| step_penalty(pair[0], pair[1]))
+			.sum(),
+	)
+}
+
+/// The unevenness of stepping a distance of `next` straight after a step of `previous`,
+/// as summed by [path_smoothness]
+pub(crate) fn step_penalty(previous: f32, next: f32) -> f32 {
+	(next - previous) * (next - previous)
+}
+
+/// Finds every node from which an edge into `end_node` lies on an optimal route from
+/// `start_node`, i.e. the alternative final steps for reaching the goal at the lowest cost.
+///
+/// The cost of a route is the total distance traveled, as the weight of `end_node` is
+/// common to every route into it. This is cheaper than enumerating all of the optimal
+/// paths when only the final edge matters, such as when rendering choices of approach.
+/// The nodes are returned in no particular order and the list is empty if `end_node`
+/// can't be reached.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::optimal_goal_predecessors;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 2.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 5.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// assert_eq!(vec![2], optimal_goal_predecessors(0, &nodes, 3));
+/// ```
+///
+/// # Panics
+///
+/// If `nodes` doesn't contain `start_node` or `end_node`
+#[allow(clippy::type_complexity)]
+pub fn optimal_goal_predecessors<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Vec<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let distances = shortest_distances(start_node, nodes);
+	let optimal = match distances.get(&end_node) {
+		Some(x) => x,
+		None => return Vec::new(),
+	};
+	let mut predecessors = Vec::new();
+	for (node, (neighbours, _)) in nodes.iter() {
+		let distance = match distances.get(node) {
+			Some(x) => x,
+			None => continue,
+		};
+		let feeds_goal = neighbours.iter().any(|(neighbour, edge)| {
+			*neighbour == end_node && distance + edge <= optimal + DEFAULT_SCORE_EPSILON
+		});
+		if feeds_goal {
+			predecessors.push(*node);
+		}
+	}
+	predecessors
+}
+
+/// Finds every node which lies on at least one optimal route from `start_node` to
+/// `end_node`, the corridor within which any optimal path must travel, such as for
+/// highlighting the choice of equally good routes.
+///
+/// The cost of a route is the total distance traveled, as per
+/// [optimal_goal_predecessors]. A node is in the corridor when its shortest distance from
+/// `start_node` plus its shortest distance to `end_node` equals the optimal distance, within
+/// the same tolerance used to compare routes, both being found in a single pass each. The
+/// set is empty if `end_node` can't be reached.
+///
+/// ```rust
+/// use std::collections::{HashMap, HashSet};
+/// use pathfinding_astar::optimal_corridor;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 2.0), (3, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(4, 2.0)], 1.0));
+/// nodes.insert(2, (vec![(4, 1.0)], 1.0));
+/// nodes.insert(3, (vec![(4, 5.0)], 1.0));
+/// nodes.insert(4, (vec![], 1.0));
+/// let expected: HashSet<i32> = [0, 1, 2, 4].iter().copied().collect();
+/// assert_eq!(expected, optimal_corridor(0, &nodes, 4));
+/// ```
+///
+/// # Panics
+///
+/// If `nodes` doesn't contain `start_node` or `end_node`
+#[allow(clippy::type_complexity)]
+pub fn optimal_corridor<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> HashSet<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let from_start = shortest_distances(start_node, nodes);
+	let optimal = match from_start.get(&end_node) {
+		Some(x) => *x,
+		None => return HashSet::new(),
+	};
+	let to_end = shortest_distances(end_node, &reversed_nodes(nodes));
+	from_start
+		.iter()
+		.filter(|(node, from)| match to_end.get(node) {
+			Some(to) => no_worse_than(*from + to, optimal, DEFAULT_SCORE_EPSILON),
+			None => false,
+		})
+		.map(|(node, _)| *node)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fixtures::{grid_nodes, large_grid_nodes, no_path_nodes};
+
+	#[test]
+	/// Of three routes across the grid the shortest is chosen, whichever order the pairs
+	/// come in, and a pair with no route is skipped
+	fn cheapest_of_three_routes() {
+		let nodes = grid_nodes();
+		let pairs = [(0, 15), (5, 6), (3, 12)];
+		let (index, path, cost) = cheapest_of(&pairs, &nodes).unwrap();
+		assert_eq!(1, index);
+		assert_eq!(vec![5, 6], path);
+		assert_eq!(edge_distance(&nodes, &5, &6), Some(cost));
+		let reversed = [(3, 12), (5, 6), (0, 15)];
+		assert_eq!(1, cheapest_of(&reversed, &nodes).unwrap().0);
+		let nodes = no_path_nodes();
+		assert_eq!(None, cheapest_of(&[(0, 5)], &nodes));
+	}
+
+	#[test]
+	/// The two neighbours of the corner of the grid are nearest, followed by one of the
+	/// three nodes two steps away
+	fn three_nearest_on_grid() {
+		let nodes = grid_nodes();
+		let nearest = k_nearest(0, &nodes, 3);
+		assert_eq!(3, nearest.len());
+		let mut adjacent = vec![nearest[0].0, nearest[1].0];
+		adjacent.sort_unstable();
+		assert_eq!(vec![1, 4], adjacent);
+		assert!([2, 5, 8].contains(&nearest[2].0));
+		let costs: Vec<f32> = nearest.iter().map(|(_, cost)| *cost).collect();
+		assert_eq!(vec![1.0, 1.0, 2.0], costs);
+		assert!(k_nearest(0, &nodes, 0).is_empty());
+		assert_eq!(15, k_nearest(0, &nodes, 20).len());
+	}
+	#[test]
+	/// A one way edge from `0` to `1` doesn't make them mutually reachable, the grid's
+	/// edges run both ways so its corners are
+	fn mutually_reachable_directed() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 1.0));
+		nodes.insert(1, (vec![], 1.0));
+		assert!(!mutually_reachable(0, 1, &nodes));
+		assert!(!mutually_reachable(1, 0, &nodes));
+		nodes.get_mut(&1).unwrap().0.push((0, 1.0));
+		assert!(mutually_reachable(0, 1, &nodes));
+		assert!(mutually_reachable(0, 15, &grid_nodes()));
+		assert!(!mutually_reachable(0, 5, &no_path_nodes()));
+	}
+	#[test]
+	/// Every monotone route across a grid of equal weights ties, rebuilding the grid with
+	/// its neighbours in a different order each run still gives the same optimal cost
+	fn optimal_cost_stable_under_ties() {
+		for run in 0..25 {
+			let nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = large_grid_nodes(6)
+				.into_iter()
+				.map(|(node, (mut neighbours, _))| {
+					let len = neighbours.len();
+					neighbours.rotate_left((run + node as usize) % len);
+					(node, (neighbours, 1.0))
+				})
+				.collect();
+			assert_eq!(Some(10.0), astar_optimal_cost(0, &nodes, 35));
+		}
+		assert_eq!(None, astar_optimal_cost(0, &no_path_nodes(), 5));
+	}
+	#[test]
+	/// Shortest distances across the grid ignore weights
+	fn grid_shortest_distances() {
+		let nodes = grid_nodes();
+		let distances = shortest_distances(0, &nodes);
+		assert_eq!(16, distances.len());
+		assert_eq!(Some(&6.0), distances.get(&15));
+		assert_eq!(Some(&3.0), distances.get(&3));
+	}
+	#[test]
+	/// The diameter of the grid is the corner to corner distance, which is the same from any
+	/// node of it
+	fn grid_diameter() {
+		let nodes = grid_nodes();
+		let corner_to_corner = shortest_distances(0, &nodes)[&15];
+		assert_eq!(6.0, corner_to_corner);
+		assert_eq!(Some(corner_to_corner), component_diameter(0, &nodes));
+		assert_eq!(Some(corner_to_corner), component_diameter(9, &nodes));
+		assert_eq!(None, component_diameter(16, &nodes));
+	}
+	#[test]
+	/// Crossing one cell of the grid diagonally has two optimal paths, the corridor holds
+	/// the nodes of both and none of the rest of the grid
+	fn corridor_of_two_optimal_paths() {
+		let nodes = large_grid_nodes(3);
+		let mut corridor: Vec<i32> = optimal_corridor(0, &nodes, 4).into_iter().collect();
+		corridor.sort();
+		assert_eq!(vec![0, 1, 3, 4], corridor);
+		assert!(optimal_corridor(0, &no_path_nodes(), 5).is_empty());
+	}
+	#[test]
+	#[should_panic(expected = "Node data does not contain end node 9")]
+	/// An end node missing from the graph is reported rather than giving an empty corridor
+	fn corridor_missing_end_node() {
+		let _ = optimal_corridor(0, &large_grid_nodes(3), 9);
+	}
+	#[test]
+	/// Nodes `1` and `2` both feed the goal at the same optimal cost while `3` is a longer
+	/// approach
+	fn two_optimal_predecessors() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 2.0), (2, 1.0), (3, 1.0)], 1.0));
+		nodes.insert(1, (vec![(4, 2.0)], 1.0));
+		nodes.insert(2, (vec![(4, 3.0)], 1.0));
+		nodes.insert(3, (vec![(4, 9.0)], 1.0));
+		nodes.insert(4, (vec![], 1.0));
+		let mut predecessors = optimal_goal_predecessors(0, &nodes, 4);
+		predecessors.sort();
+		assert_eq!(vec![1, 2], predecessors);
+	}
+	#[test]
+	/// An unreachable goal has no optimal predecessors
+	fn unreachable_goal_predecessors() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![], 1.0));
+		nodes.insert(1, (vec![(0, 1.0)], 1.0));
+		assert!(optimal_goal_predecessors(0, &nodes, 1).is_empty());
+	}
+	#[test]
+	/// The corner opposite node `0` is the furthest node of the grid
+	fn grid_farthest_node() {
+		let nodes = grid_nodes();
+		assert_eq!(Some((15, 6.0)), farthest_node(0, &nodes));
+		assert_eq!(Some((0, 6.0)), farthest_node(15, &nodes));
+		assert_eq!(None, farthest_node(16, &nodes));
+	}
+	#[test]
+	/// Around a one way ring the distance out to a node and back from it differ, while the
+	/// two way spoke is the same in both directions
+	fn both_trees_on_one_way_ring() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (4, 2.0)], 1.0));
+		nodes.insert(1, (vec![(2, 1.0)], 1.0));
+		nodes.insert(2, (vec![(3, 1.0)], 1.0));
+		nodes.insert(3, (vec![(0, 1.0)], 1.0));
+		nodes.insert(4, (vec![(0, 2.0)], 1.0));
+		nodes.insert(5, (vec![(0, 1.0)], 1.0));
+		let (from_center, to_center) = both_trees(0, &nodes);
+		assert_eq!(Some(&1.0), from_center.get(&1));
+		assert_eq!(Some(&3.0), to_center.get(&1));
+		assert_eq!(Some(&3.0), from_center.get(&3));
+		assert_eq!(Some(&1.0), to_center.get(&3));
+		assert_eq!(from_center.get(&4), to_center.get(&4));
+		// 5 can reach the center but can't be reached from it
+		assert_eq!(None, from_center.get(&5));
+		assert_eq!(Some(&1.0), to_center.get(&5));
+		assert_eq!(Some(&0.0), to_center.get(&0));
+	}
+	#[test]
+	/// A budget of two units from the corner of the grid reaches only the nearby cells
+	fn grid_reachable_within_cost() {
+		let reachable = reachable_within_cost(0, &grid_nodes(), 2.0);
+		let mut expected = HashMap::new();
+		expected.insert(0, 0.0);
+		expected.insert(1, 1.0);
+		expected.insert(4, 1.0);
+		expected.insert(2, 2.0);
+		expected.insert(5, 2.0);
+		expected.insert(8, 2.0);
+		assert_eq!(expected, reachable);
+	}
+	#[test]
+	/// The cached grid path stops being optimal once an edge along it becomes longer
+	/// than a detour
+	fn grid_path_no_longer_optimal() {
+		let mut nodes = grid_nodes();
+		let path = crate::astar_path(0, &nodes, 15).unwrap();
+		assert!(is_path_still_optimal(&path, &nodes));
+		// the edge from 8 to 9 is made expensive so routes around it are now shorter
+		for (neighbour, distance) in nodes.get_mut(&8).unwrap().0.iter_mut() {
+			if *neighbour == 9 {
+				*distance = 10.0;
+			}
+		}
+		assert!(!is_path_still_optimal(&path, &nodes));
+	}
+}
@@ -0,0 +1,407 @@
+//! Searches which find the shortest path by distance alone, for graphs where node weights
+//! offer no useful guidance
+//!
+
+use std::{
+	collections::{BinaryHeap, HashMap},
+	fmt::Debug,
+	hash::Hash,
+};
+
+use crate::{
+	check_endpoints,
+	costs::{CostMap, MinDistance},
+	error::AstarError,
+	graph::{build_reverse, edge_distance},
+	heuristics::{astar_path_with_heuristic, NoHeuristic},
+};
+
+/// Finds the path from `start_node` to `end_node` with the shortest total distance,
+/// ignoring the weight of every node. Equivalent to [astar_path](crate::astar_path) with
+/// all weights set to zero.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::dijkstra_path;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 4.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 100.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// assert_eq!(Some(vec![0, 1, 2]), dijkstra_path(0, &nodes, 2));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn dijkstra_path<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_path_with_heuristic(start_node, nodes, end_node, NoHeuristic)
+}
+
+/// Finds the shortest cycle which leaves `node` and returns to it, visiting at least one
+/// other node on the way, such as for generating a patrol loop. The cycle is given as a
+/// path which begins and ends on `node`.
+///
+/// The cost of a cycle is its total distance, node weights are ignored. Each neighbour of
+/// `node` is tried in turn as the first step, followed by the shortest path from it back
+/// to `node`. An edge from `node` to itself doesn't count as a cycle and `None` is
+/// returned if no neighbour has a path back.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::cheapest_cycle_through;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(0, 5.0)], 1.0));
+/// nodes.insert(2, (vec![(0, 2.0)], 1.0));
+/// assert_eq!(Some(vec![0, 2, 0]), cheapest_cycle_through(0, &nodes));
+/// ```
+///
+/// # Panics
+///
+/// If `nodes` doesn't contain `node`, otherwise under the same conditions as
+/// [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn cheapest_cycle_through<T>(
+	node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let neighbours = match nodes.get(&node) {
+		Some(x) => &x.0,
+		None => panic!("{}", AstarError::MissingStartNode(node)),
+	};
+	let mut cheapest: Option<(f32, Vec<T>)> = None;
+	for (neighbour, distance) in neighbours.iter() {
+		if *neighbour == node {
+			continue;
+		}
+		let path = match dijkstra_path(*neighbour, nodes, node) {
+			Some(x) => x,
+			None => continue,
+		};
+		let cost = distance
+			+ path
+				.windows(2)
+				.map(|step| edge_distance(nodes, &step[0], &step[1]))
+				.sum::<Option<f32>>()
+				.expect("every hop of a found path is an edge of `nodes`");
+		if cheapest.as_ref().map_or(true, |(best, _)| cost < *best) {
+			let mut cycle = vec![node];
+			cycle.extend(path);
+			cheapest = Some((cost, cycle));
+		}
+	}
+	cheapest.map(|(_, cycle)| cycle)
+}
+
+/// Finds the path from `start_node` to `end_node` with the shortest total distance, as
+/// per [dijkstra_path], by searching outward from both ends at once and meeting in the
+/// middle. This typically settles far fewer nodes than searching from one end when no
+/// heuristic is available.
+///
+/// The search from `end_node` travels the edges of `nodes` backwards. Where several
+/// paths share the shortest distance any one of them may be returned.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::dijkstra_bidirectional;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 4.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 100.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// assert_eq!(Some(vec![0, 1, 2]), dijkstra_bidirectional(0, &nodes, 2));
+/// ```
+///
+/// # Panics
+///
+/// If `nodes` doesn't contain `start_node` or `end_node`, or an edge has a negative or
+/// `NaN` distance
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn dijkstra_bidirectional<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	dijkstra_bidirectional_with_reverse(start_node, nodes, end_node, &build_reverse(nodes))
+}
+
+/// Behaves like [dijkstra_bidirectional] but travels backwards from `end_node` over the
+/// precomputed `reverse` edges, as given by [build_reverse](crate::build_reverse), rather
+/// than rebuilding them for each query. `reverse` must be kept in step with `nodes`.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{build_reverse, dijkstra_bidirectional_with_reverse};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 4.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 100.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// let reverse = build_reverse(&nodes);
+/// for end in 0..3 {
+///     let path = dijkstra_bidirectional_with_reverse(0, &nodes, end, &reverse);
+///     assert_eq!(Some(&end), path.unwrap().last());
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [dijkstra_bidirectional]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn dijkstra_bidirectional_with_reverse<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	reverse: &HashMap<T, Vec<(T, f32)>>,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let mut forward = DirectionalSearch::new(start_node, nodes);
+	let mut backward = DirectionalSearch::new(end_node, reverse);
+	// the shortest distance of a complete path found so far and the node where the two
+	// searches met along it
+	let mut best: Option<(f32, T)> = None;
+	if start_node == end_node {
+		best = Some((0.0, start_node));
+	}
+	// once either side has exhausted everything it can reach no shorter meeting exists
+	while let (Some(forward_min), Some(backward_min)) = (forward.min_key(), backward.min_key()) {
+		// any path not yet found must be at least as long as the sum of the two frontiers
+		if let Some((distance, _)) = best {
+			if forward_min + backward_min >= distance {
+				break;
+			}
+		}
+		// advance whichever side has the smaller frontier key
+		let settled = if forward_min <= backward_min {
+			forward.settle_next(&backward.distances)
+		} else {
+			backward.settle_next(&forward.distances)
+		};
+		if let Some(meeting) = settled {
+			if best.map_or(true, |(distance, _)| meeting.0 < distance) {
+				best = Some(meeting);
+			}
+		}
+	}
+	let (_, meeting_node) = best?;
+	let mut path = forward.path_to(meeting_node);
+	let mut remainder = backward.path_to(meeting_node);
+	// the backward path runs from the end to the meeting node which is already present
+	remainder.pop();
+	remainder.reverse();
+	path.extend(remainder);
+	Some(path)
+}
+
+/// The edges leading out of each node in the direction of a [DirectionalSearch], read in
+/// place from the graph being searched
+trait Edges<T> {
+	/// The `(neighbour, distance)` pairs leading out of `node`, `None` if it has no entry
+	fn edges(&self, node: &T) -> Option<&[(T, f32)]>;
+}
+
+/// The forward edges of a `nodes` data set
+impl<T: Eq + Hash> Edges<T> for HashMap<T, (Vec<(T, f32)>, f32)> {
+	fn edges(&self, node: &T) -> Option<&[(T, f32)]> {
+		self.get(node).map(|(neighbours, _)| neighbours.as_slice())
+	}
+}
+
+/// The backward edges given by [build_reverse](crate::build_reverse)
+impl<T: Eq + Hash> Edges<T> for HashMap<T, Vec<(T, f32)>> {
+	fn edges(&self, node: &T) -> Option<&[(T, f32)]> {
+		self.get(node).map(|edges| edges.as_slice())
+	}
+}
+
+/// One half of [dijkstra_bidirectional], a Dijkstra search outward from `source` over
+/// `edges`
+struct DirectionalSearch<'a, T, E> {
+	/// The edges leading out of each node in the direction of the search
+	edges: &'a E,
+	/// Shortest distance found so far from the source to each node
+	distances: CostMap<T>,
+	/// The node each node was reached from along its shortest route
+	previous: HashMap<T, T>,
+	/// Nodes awaiting settlement ordered by distance, including stale entries
+	heap: BinaryHeap<MinDistance<T>>,
+}
+
+impl<'a, T, E> DirectionalSearch<'a, T, E>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	E: Edges<T>,
+{
+	/// A search which has yet to settle `source`
+	fn new(source: T, edges: &'a E) -> Self {
+		let mut distances = HashMap::new();
+		distances.insert(source, 0.0);
+		let mut heap = BinaryHeap::new();
+		heap.push(MinDistance {
+			distance: 0.0,
+			node: source,
+		});
+		DirectionalSearch {
+			edges,
+			distances,
+			previous: HashMap::new(),
+			heap,
+		}
+	}
+	/// Discards stale entries and gives the distance of the next node to settle
+	fn min_key(&mut self) -> Option<f32> {
+		while let Some(head) = self.heap.peek() {
+			if head.distance > self.distances[&head.node] {
+				self.heap.pop();
+			} else {
+				return Some(head.distance);
+			}
+		}
+		None
+	}
+	/// Settles the next node and relaxes its edges. Returns the shortest complete path
+	/// discovered through an edge where it meets a node reached by the opposite search,
+	/// whose shortest distances are `opposite`, as the total distance and the node on the far
+	/// side of that edge
+	fn settle_next(&mut self, opposite: &CostMap<T>) -> Option<(f32, T)> {
+		let MinDistance { distance, node } = self.heap.pop()?;
+		let mut meeting: Option<(f32, T)> = None;
+		if let Some(other) = opposite.get(&node) {
+			meeting = Some((distance + other, node));
+		}
+		let edges = match self.edges.edges(&node) {
+			Some(x) => x,
+			None => return meeting,
+		};
+		for (neighbour, edge) in edges.iter() {
+			if edge.is_nan() {
+				panic!("{}", AstarError::NanScore(*neighbour));
+			}
+			if *edge < 0.0 {
+				panic!("{}", AstarError::NegativeDistance(*neighbour));
+			}
+			let distance_traveled = distance + edge;
+			let improved = match self.distances.get(neighbour) {
+				Some(existing) => distance_traveled < *existing,
+				None => true,
+			};
+			if improved {
+				self.distances.insert(*neighbour, distance_traveled);
+				self.previous.insert(*neighbour, node);
+				self.heap.push(MinDistance {
+					distance: distance_traveled,
+					node: *neighbour,
+				});
+			}
+			if let Some(other) = opposite.get(neighbour) {
+				let total = self.distances[neighbour] + other;
+				if meeting.map_or(true, |(best, _)| total < best) {
+					meeting = Some((total, *neighbour));
+				}
+			}
+		}
+		meeting
+	}
+	/// The nodes from the source of the search through to `node` inclusive
+	fn path_to(&self, node: T) -> Vec<T> {
+		let mut path = vec![node];
+		let mut current = node;
+		while let Some(previous) = self.previous.get(&current) {
+			path.push(*previous);
+			current = *previous;
+		}
+		path.reverse();
+		path
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fixtures::{grid_nodes, large_grid_nodes, no_path_nodes};
+
+	/// Total distance along `path` through `nodes`
+	#[allow(clippy::type_complexity)]
+	fn path_distance(path: &[i32], nodes: &HashMap<i32, (Vec<(i32, f32)>, f32)>) -> f32 {
+		path.windows(2)
+			.map(|step| edge_distance(nodes, &step[0], &step[1]).unwrap())
+			.sum()
+	}
+
+	#[test]
+	/// Searching from both ends agrees exactly with the cost of searching from one end
+	/// between every pair of nodes of the grid
+	fn bidirectional_agrees_with_dijkstra() {
+		let nodes = grid_nodes();
+		for start in 0..16 {
+			for end in 0..16 {
+				let single = dijkstra_path(start, &nodes, end).unwrap();
+				let both = dijkstra_bidirectional(start, &nodes, end).unwrap();
+				assert_eq!(start, both[0]);
+				assert_eq!(end, *both.last().unwrap());
+				assert_eq!(path_distance(&single, &nodes), path_distance(&both, &nodes));
+			}
+		}
+	}
+	#[test]
+	/// Searching from both ends agrees on a larger grid with uneven distances
+	#[allow(clippy::type_complexity)]
+	fn bidirectional_agrees_on_uneven_grid() {
+		let mut nodes = large_grid_nodes(12);
+		for (node, (neighbours, _)) in nodes.iter_mut() {
+			for (neighbour, distance) in neighbours.iter_mut() {
+				*distance = 1.0 + ((node * 7 + *neighbour * 3) % 5) as f32;
+			}
+		}
+		let single = dijkstra_path(3, &nodes, 140).unwrap();
+		let both = dijkstra_bidirectional(3, &nodes, 140).unwrap();
+		assert_eq!(path_distance(&single, &nodes), path_distance(&both, &nodes));
+	}
+	#[test]
+	/// Neither search finds a path where none exists
+	fn bidirectional_no_path() {
+		let nodes = no_path_nodes();
+		assert_eq!(None, dijkstra_path(0, &nodes, 5));
+		assert_eq!(None, dijkstra_bidirectional(0, &nodes, 5));
+	}
+	#[test]
+	/// Of the loops around a ring with a chord the shortest is chosen, it begins and ends on
+	/// the given node
+	fn cheapest_cycle_on_ring() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(0, 0.5), (1, 1.0), (3, 4.0)], 1.0));
+		nodes.insert(1, (vec![(2, 1.0)], 1.0));
+		nodes.insert(2, (vec![(3, 1.0), (0, 3.0)], 1.0));
+		nodes.insert(3, (vec![(0, 1.0)], 1.0));
+		nodes.insert(4, (vec![(0, 1.0)], 1.0));
+		let cycle = cheapest_cycle_through(0, &nodes).unwrap();
+		assert_eq!(vec![0, 1, 2, 3, 0], cycle);
+		assert_eq!(4.0, path_distance(&cycle, &nodes));
+		assert_eq!(Some(vec![1, 2, 3, 0, 1]), cheapest_cycle_through(1, &nodes));
+		// nothing leads back to 4
+		assert_eq!(None, cheapest_cycle_through(4, &nodes));
+	}
+}
@@ -0,0 +1,253 @@
+//! Helpers for rendering paths and graphs in a human readable form for debugging and logging
+//!
+
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::graph::edge_distance;
+
+/// Renders a path as its node labels joined by arrows, e.g. `"0 -> 4 -> 8 -> 15"`.
+///
+/// ```rust
+/// use pathfinding_astar::format_path;
+///
+/// assert_eq!("0 -> 4 -> 8", format_path(&[0, 4, 8]));
+/// assert_eq!("(0, 0) -> (0, 1)", format_path(&[(0, 0), (0, 1)]));
+/// ```
+pub fn format_path<T>(path: &[T]) -> String
+where
+	T: Debug,
+{
+	path.iter()
+		.map(|node| format!("{:?}", node))
+		.collect::<Vec<String>>()
+		.join(" -> ")
+}
+
+/// Renders a path as its node labels joined by arrows with the cumulative distance
+/// traveled to reach each node in brackets, e.g. `"0 (0) -> 4 (1) -> 8 (2)"`.
+///
+/// The distances are looked up from `nodes`, where a node has several edges to the next
+/// node of the path the shortest is used. Returns `None` if two consecutive nodes of the
+/// path are not connected.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::format_path_with_costs;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.5)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.5)], 6.0));
+/// let formatted = format_path_with_costs(&[0, 1, 2], &nodes).unwrap();
+/// assert_eq!("0 (0) -> 1 (5) -> 2 (9.5)", formatted);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn format_path_with_costs<T>(
+	path: &[T],
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> Option<String>
+where
+	T: Eq + Hash + Debug,
+{
+	let mut segments = Vec::with_capacity(path.len());
+	let mut distance_traveled = 0.0;
+	for (i, node) in path.iter().enumerate() {
+		if i > 0 {
+			distance_traveled += edge_distance(nodes, &path[i - 1], node)?;
+		}
+		segments.push(format!("{:?} ({})", node, distance_traveled));
+	}
+	Some(segments.join(" -> "))
+}
+
+/// Splits `path` into segments at every node whose weight in `nodes` exceeds `threshold`,
+/// such as for marking rest stops or difficult waypoints along a route.
+///
+/// A node at which the path is split ends one segment and begins the next, so consecutive
+/// heavy nodes give a segment of just the two of them. The first and last nodes of the path
+/// already bound it and never cause a split, nor do nodes missing from `nodes`. When no
+/// node exceeds `threshold` the whole path is a single segment and an empty path gives no
+/// segments.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::segment_path_at;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 9.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// assert_eq!(vec![vec![0, 1], vec![1, 2]], segment_path_at(&[0, 1, 2], &nodes, 5.0));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn segment_path_at<T>(
+	path: &[T],
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	threshold: f32,
+) -> Vec<Vec<T>>
+where
+	T: Eq + Hash + Clone,
+{
+	let mut segments = Vec::new();
+	if path.is_empty() {
+		return segments;
+	}
+	let mut segment = vec![path[0].clone()];
+	for (i, node) in path.iter().enumerate().skip(1) {
+		segment.push(node.clone());
+		let heavy = nodes
+			.get(node)
+			.map_or(false, |(_, weight)| *weight > threshold);
+		if heavy && i < path.len() - 1 {
+			segments.push(segment);
+			segment = vec![node.clone()];
+		}
+	}
+	segments.push(segment);
+	segments
+}
+
+/// Renders `nodes` in the Graphviz DOT language, labelling each node with its weight and
+/// each edge with its distance. Edges traveled along the optional `highlight` path, along
+/// with the nodes it visits, are drawn in red to make it easy to see why a path was chosen.
+///
+/// Nodes are identified by their `Debug` representation and are listed in no particular
+/// order.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::to_dot;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![], 2.0));
+/// let dot = to_dot(&nodes, Some(&[0, 1]));
+/// assert!(dot.starts_with("digraph {"));
+/// assert!(dot.contains("\"0\" -> \"1\" [label=\"5\", color=red, penwidth=2];"));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn to_dot<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>, highlight: Option<&[T]>) -> String
+where
+	T: Eq + Hash + Debug,
+{
+	let highlight = highlight.unwrap_or(&[]);
+	let on_path = |from: &T, to: &T| {
+		highlight
+			.windows(2)
+			.any(|step| step[0] == *from && step[1] == *to)
+	};
+	let mut dot = String::from("digraph {\n");
+	for (node, (neighbours, weight)) in nodes.iter() {
+		let style = if highlight.contains(node) {
+			", color=red"
+		} else {
+			""
+		};
+		dot.push_str(&format!(
+			"    {} [label=\"{} (w: {})\"{}];\n",
+			dot_id(node),
+			escape_dot(&format!("{:?}", node)),
+			weight,
+			style
+		));
+		for (neighbour, distance) in neighbours.iter() {
+			let style = if on_path(node, neighbour) {
+				", color=red, penwidth=2"
+			} else {
+				""
+			};
+			dot.push_str(&format!(
+				"    {} -> {} [label=\"{}\"{}];\n",
+				dot_id(node),
+				dot_id(neighbour),
+				distance,
+				style
+			));
+		}
+	}
+	dot.push('}');
+	dot
+}
+
+/// The quoted DOT identifier of a node, taken from its `Debug` representation
+fn dot_id<T: Debug>(node: &T) -> String {
+	format!("\"{}\"", escape_dot(&format!("{:?}", node)))
+}
+
+/// Escapes the characters of `text` which would end a quoted DOT string early
+fn escape_dot(text: &str) -> String {
+	text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::astar_path;
+	use crate::fixtures::grid_nodes;
+
+	#[test]
+	/// The grid path crosses cell `10` with a weight of `4`, splitting there gives two
+	/// segments sharing it, while a higher threshold leaves the path whole
+	fn segment_grid_path() {
+		let nodes = grid_nodes();
+		let path = astar_path(0, &nodes, 15).unwrap();
+		assert_eq!(
+			vec![vec![0, 4, 8, 9, 10], vec![10, 11, 15]],
+			segment_path_at(&path, &nodes, 3.5)
+		);
+		assert_eq!(vec![path.clone()], segment_path_at(&path, &nodes, 10.0));
+		// 10 and 11 both exceed a threshold of 2.5 and are consecutive
+		assert_eq!(
+			vec![vec![0, 4, 8, 9, 10], vec![10, 11], vec![11, 15]],
+			segment_path_at(&path, &nodes, 2.5)
+		);
+		assert!(segment_path_at(&[], &nodes, 1.0).is_empty());
+	}
+
+	#[test]
+	/// The grid path is rendered as an arrow-joined sequence
+	fn format_grid_path() {
+		let nodes = grid_nodes();
+		let path = astar_path(0, &nodes, 15).unwrap();
+		assert_eq!("0 -> 4 -> 8 -> 9 -> 10 -> 11 -> 15", format_path(&path));
+	}
+	#[test]
+	/// The grid path is rendered with the cumulative distance to each node
+	fn format_grid_path_with_costs() {
+		let nodes = grid_nodes();
+		let path = astar_path(0, &nodes, 15).unwrap();
+		assert_eq!(
+			Some("0 (0) -> 4 (1) -> 8 (2) -> 9 (3) -> 10 (4) -> 11 (5) -> 15 (6)".to_string()),
+			format_path_with_costs(&path, &nodes)
+		);
+	}
+	#[test]
+	/// Empty paths render as an empty string and disconnected paths can't be costed
+	fn format_edge_cases() {
+		let nodes = grid_nodes();
+		assert_eq!("", format_path::<i32>(&[]));
+		assert_eq!(None, format_path_with_costs(&[0, 15], &nodes));
+	}
+	#[test]
+	/// The DOT rendering of the grid includes the start node and the edges of the path
+	/// are highlighted
+	fn grid_to_dot() {
+		let nodes = grid_nodes();
+		let path = astar_path(0, &nodes, 15).unwrap();
+		let dot = to_dot(&nodes, Some(&path));
+		assert!(!dot.is_empty());
+		assert!(dot.contains("\"0\" [label=\"0 (w: 1)\", color=red];"));
+		assert!(dot.contains("\"0\" -> \"4\" [label=\"1\", color=red, penwidth=2];"));
+		assert!(dot.contains("\"0\" -> \"1\" [label=\"1\"];"));
+		let plain = to_dot(&nodes, None);
+		assert!(!plain.contains("red"));
+	}
+	#[test]
+	/// Quotes within a label are escaped so they don't end the DOT identifier
+	#[allow(clippy::type_complexity)]
+	fn dot_escapes_quotes() {
+		let mut nodes: HashMap<&str, (Vec<(&str, f32)>, f32)> = HashMap::new();
+		nodes.insert("a", (vec![], 1.0));
+		assert!(to_dot(&nodes, None).contains("\"\\\"a\\\"\" [label="));
+	}
+}
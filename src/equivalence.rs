@@ -0,0 +1,186 @@
+//! Searches which identify nodes by a caller supplied equality rather than the `Eq` of
+//! their labels, for interop with data keyed by an external rule
+//!
+
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::{error::AstarError, search, SearchStats, DEFAULT_SCORE_EPSILON};
+
+/// Behaves like [astar_path](crate::astar_path) except that two labels refer to the same
+/// node whenever `eq` says so, such as case-insensitive strings, decoupling the identity
+/// of a node from the derived `Eq` of its label.
+///
+/// `hash` must give equal hashes for any two labels which are `eq`, as with `Hash` and
+/// `Eq`. Neighbours, the start node and the end node are each matched to a key of `nodes`
+/// with `eq` and the path is made of those keys. Labels don't need to be `Copy`. Should
+/// `nodes` hold several keys which are `eq` to one another, which of them a label matches
+/// is unspecified.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_with_eq;
+///
+/// let mut nodes: HashMap<String, (Vec<(String, f32)>, f32)> = HashMap::new();
+/// nodes.insert("A".to_string(), (vec![("b".to_string(), 1.0)], 1.0));
+/// nodes.insert("B".to_string(), (vec![], 1.0));
+/// let path = astar_path_with_eq(
+///     "a".to_string(),
+///     &nodes,
+///     "B".to_string(),
+///     |x: &String, y: &String| x.eq_ignore_ascii_case(y),
+///     |x: &String| x.bytes().map(|b| b.to_ascii_lowercase() as u64).sum(),
+/// );
+/// assert_eq!(Some(vec!["A".to_string(), "B".to_string()]), path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path), where a node is missing
+/// if no key of `nodes` is `eq` to it
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_eq<T, E, H>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	eq: E,
+	hash: H,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone,
+	E: Fn(&T, &T) -> bool,
+	H: Fn(&T) -> u64,
+{
+	// search over the position of each key in `entries`, which unlike labels are `Copy`
+	let entries: Vec<(&T, &(Vec<(T, f32)>, f32))> = nodes.iter().collect();
+	let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+	for (index, (label, _)) in entries.iter().enumerate() {
+		buckets.entry(hash(label)).or_default().push(index);
+	}
+	let index_of = |label: &T| -> Option<usize> {
+		buckets
+			.get(&hash(label))?
+			.iter()
+			.copied()
+			.find(|index| eq(entries[*index].0, label))
+	};
+	path_by_index(start_node, end_node, index_of, |index| entries[index])
+}
+
+/// Runs the search of [astar_path](crate::astar_path) over the position of each node
+/// rather than its label, for labels which aren't `Copy` or whose identity isn't their
+/// `Eq`. `index_of` gives the position of the node a label refers to, `None` if it is
+/// missing, and `entry` the label and data of the node at a position. Everything the search
+/// tracks is keyed and ordered by position alone, and the path is mapped back to the labels
+/// given by `entry`.
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path), where a node is missing
+/// if `index_of` gives no position for it
+#[allow(clippy::type_complexity)]
+pub(crate) fn path_by_index<'a, T, I, E>(
+	start_node: T,
+	end_node: T,
+	index_of: I,
+	entry: E,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + 'a,
+	I: Fn(&T) -> Option<usize>,
+	E: Fn(usize) -> (&'a T, &'a (Vec<(T, f32)>, f32)),
+{
+	let start = match index_of(&start_node) {
+		Some(x) => x,
+		None => panic!("{}", AstarError::MissingStartNode(start_node)),
+	};
+	let end = match index_of(&end_node) {
+		Some(x) => x,
+		None => panic!("{}", AstarError::MissingEndNode(end_node)),
+	};
+	search(
+		start,
+		|index, _, _| *index == end,
+		|index| {
+			let neighbours: Vec<(usize, f32)> = (entry(*index).1)
+				.0
+				.iter()
+				.map(|(label, distance)| match index_of(label) {
+					Some(neighbour) => (neighbour, *distance),
+					None => panic!("{}", AstarError::MissingNode(label.clone())),
+				})
+				.collect();
+			Ok(neighbours)
+		},
+		|index| {
+			let weight = (entry(*index).1).1;
+			Ok((weight, weight))
+		},
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e.map_nodes(|index| entry(index).0.clone())))
+	.into_option()
+	.map(|path| {
+		path.into_iter()
+			.map(|index| entry(index).0.clone())
+			.collect()
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Compares labels ignoring ASCII case
+	#[allow(clippy::ptr_arg)]
+	fn same_letters(x: &String, y: &String) -> bool {
+		x.eq_ignore_ascii_case(y)
+	}
+
+	/// Hashes the lowercase form of a label so labels differing in case hash alike
+	#[allow(clippy::ptr_arg)]
+	fn lowercase_hash(x: &String) -> u64 {
+		x.bytes().fold(0, |h: u64, b| {
+			h.wrapping_mul(31)
+				.wrapping_add(b.to_ascii_lowercase() as u64)
+		})
+	}
+
+	#[test]
+	/// Neighbours referenced in lowercase reach the uppercase keys, and a start of `"a"` is
+	/// the node `"A"`
+	fn case_insensitive_labels() {
+		let label = |x: &str| x.to_string();
+		let mut nodes: HashMap<String, (Vec<(String, f32)>, f32)> = HashMap::new();
+		nodes.insert(
+			label("A"),
+			(vec![(label("b"), 1.0), (label("c"), 4.0)], 1.0),
+		);
+		nodes.insert(label("B"), (vec![(label("d"), 5.0)], 1.0));
+		nodes.insert(label("C"), (vec![(label("D"), 1.0)], 1.0));
+		nodes.insert(label("D"), (vec![(label("a"), 1.0)], 1.0));
+		let path = astar_path_with_eq(label("a"), &nodes, label("d"), same_letters, lowercase_hash);
+		assert_eq!(Some(vec![label("A"), label("C"), label("D")]), path);
+		let path = astar_path_with_eq(label("d"), &nodes, label("A"), same_letters, lowercase_hash);
+		assert_eq!(Some(vec![label("D"), label("A")]), path);
+		// the derived `Eq` alone finds no such start node
+		assert!(!nodes.contains_key("a"));
+	}
+	#[test]
+	#[should_panic(expected = "\"e\" is not a key")]
+	/// A neighbour matching no key is reported by its own label
+	fn missing_neighbour_label() {
+		let mut nodes: HashMap<String, (Vec<(String, f32)>, f32)> = HashMap::new();
+		nodes.insert("A".to_string(), (vec![("e".to_string(), 1.0)], 1.0));
+		nodes.insert("B".to_string(), (vec![], 1.0));
+		let _ = astar_path_with_eq(
+			"a".to_string(),
+			&nodes,
+			"b".to_string(),
+			same_letters,
+			lowercase_hash,
+		);
+	}
+}
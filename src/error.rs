@@ -0,0 +1,186 @@
+//! Errors describing why a search could not be carried out
+//!
+
+use std::{error::Error, fmt};
+
+/// Reasons a search can fail when given malformed data, returned by the `try_` family of
+/// functions such as [try_astar_path](crate::try_astar_path) in place of a panic
+#[derive(Debug, Clone, PartialEq)]
+pub enum AstarError<T> {
+	/// The `nodes` data set does not contain the start node
+	MissingStartNode(T),
+	/// The `nodes` data set does not contain the end node
+	MissingEndNode(T),
+	/// A node referenced as a neighbour is not a key of the `nodes` data set so its weight
+	/// and neighbours are unknown
+	MissingNode(T),
+	/// The start node has a `NaN` weight, which can't be ranked should a route return to it
+	NanScore(T),
+	/// Extending a route from `from` to its neighbour `to` produced a `NaN` score, typically
+	/// from a `NaN` distance on the edge or weight of `to`, which can't be ranked against
+	/// other routes
+	NanRoute {
+		/// The node being expanded
+		from: T,
+		/// The neighbour the route was extended to
+		to: T,
+		/// A-Star score of the route which reached `from`
+		from_score: f32,
+		/// Score of the route extended to `to`, `NaN` unless only the stored weight of `to`
+		/// was `NaN`
+		score: f32,
+	},
+	/// An edge leading to a node has a negative distance, allowing a cycle to shrink the
+	/// distance traveled without end
+	NegativeDistance(T),
+}
+
+impl<T> AstarError<T> {
+	/// Relabels the nodes named by the error with `f`, for searches run over stand-in labels
+	/// which need reporting in terms of the caller's own
+	pub(crate) fn map_nodes<U, F: Fn(T) -> U>(self, f: F) -> AstarError<U> {
+		match self {
+			AstarError::MissingStartNode(node) => AstarError::MissingStartNode(f(node)),
+			AstarError::MissingEndNode(node) => AstarError::MissingEndNode(f(node)),
+			AstarError::MissingNode(node) => AstarError::MissingNode(f(node)),
+			AstarError::NanScore(node) => AstarError::NanScore(f(node)),
+			AstarError::NanRoute {
+				from,
+				to,
+				from_score,
+				score,
+			} => AstarError::NanRoute {
+				from: f(from),
+				to: f(to),
+				from_score,
+				score,
+			},
+			AstarError::NegativeDistance(node) => AstarError::NegativeDistance(f(node)),
+		}
+	}
+}
+
+impl<T: fmt::Debug> fmt::Display for AstarError<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			AstarError::MissingStartNode(node) => {
+				write!(f, "Node data does not contain start node {:?}", node)
+			}
+			AstarError::MissingEndNode(node) => {
+				write!(f, "Node data does not contain end node {:?}", node)
+			}
+			AstarError::MissingNode(node) => write!(
+				f,
+				"Node {:?} is not a key in the `nodes` data set, unable to find its weight or neighbours",
+				node
+			),
+			AstarError::NanScore(node) => write!(
+				f,
+				"The A-Star score of node {:?} is NaN, a distance or weight is probably NaN",
+				node
+			),
+			AstarError::NanRoute {
+				from,
+				to,
+				from_score,
+				score,
+			} => write!(
+				f,
+				"The route from node {:?}, scoring {}, to node {:?} scored {}, the distance between them or the weight of node {:?} is probably NaN",
+				from, from_score, to, score, to
+			),
+			AstarError::NegativeDistance(node) => write!(
+				f,
+				"An edge leading to node {:?} has a negative distance, distances must be zero or greater",
+				node
+			),
+		}
+	}
+}
+
+impl<T: fmt::Debug> Error for AstarError<T> {}
+
+/// Returned by [astar_path_result](crate::astar_path_result) when no route to the end node
+/// exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NoPath;
+
+impl fmt::Display for NoPath {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "No path exists from the start node to the end node")
+	}
+}
+
+impl Error for NoPath {}
+
+/// Structural problems found by [GraphBuilder::build](crate::GraphBuilder::build) which
+/// would otherwise cause a search over the graph to fail
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError<T> {
+	/// Edges, as `(from, to)` pairs, where one or both nodes were never added with a weight
+	DanglingEdges(Vec<(T, T)>),
+	/// Nodes added with a `NaN` weight
+	NanWeights(Vec<T>),
+	/// Edges, as `(from, to)` pairs, whose distance is negative or `NaN`
+	InvalidDistances(Vec<(T, T)>),
+	/// A node was given two different weights, the first conflict found is reported
+	ConflictingWeight {
+		/// The node given more than one weight
+		node: T,
+		/// The weight it was given first
+		first: f32,
+		/// The differing weight it was given afterwards
+		second: f32,
+	},
+}
+
+impl<T: fmt::Debug> fmt::Display for BuildError<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			BuildError::DanglingEdges(edges) => write!(
+				f,
+				"Edges {:?} reference nodes which were never added with a weight",
+				edges
+			),
+			BuildError::NanWeights(nodes) => write!(f, "Nodes {:?} have a NaN weight", nodes),
+			BuildError::InvalidDistances(edges) => {
+				write!(f, "Edges {:?} have a negative or NaN distance", edges)
+			}
+			BuildError::ConflictingWeight {
+				node,
+				first,
+				second,
+			} => write!(
+				f,
+				"Node {:?} was given conflicting weights of {} and {}",
+				node, first, second
+			),
+		}
+	}
+}
+
+impl<T: fmt::Debug> Error for BuildError<T> {}
+
+/// Reasons [GraphBuilder::build_from_stream](crate::GraphBuilder::build_from_stream) can
+/// fail to produce a graph
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamError<T, E> {
+	/// The stream of edges produced an error, loading stopped at the first one
+	Stream(E),
+	/// Every edge was received but the graph they form is malformed
+	Build(BuildError<T>),
+}
+
+#[cfg(feature = "async")]
+impl<T: fmt::Debug, E: fmt::Display> fmt::Display for StreamError<T, E> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			StreamError::Stream(error) => write!(f, "Failed to stream the edges: {}", error),
+			StreamError::Build(error) => error.fmt(f),
+		}
+	}
+}
+
+#[cfg(feature = "async")]
+impl<T: fmt::Debug, E: fmt::Debug + fmt::Display> Error for StreamError<T, E> {}
@@ -0,0 +1,246 @@
+//! Graphs shared by the tests of each module
+//!
+
+use std::collections::HashMap;
+
+/// Builds the 4x4 grid from the README, distance between each square is a unit of 1.0:
+/// ```txt
+/// ________________________
+/// | L:12| L:13| L:14| L:15|
+/// | W:5 | W:8 | W:9 | W:4 |
+/// |_____|_____|_____|_____|
+/// | L:8 | L:9 | L:10| L:11|
+/// | W:1 | W:1 | W:4 | W:3 |
+/// |_____|_____|_____|_____|
+/// | L:4 | L:5 | L:6 | L:7 |
+/// | W:1 | W:9 | W:14| W:6 |
+/// |_____|_____|_____|_____|
+/// | L:0 | L:1 | L:2 | L:3 |
+/// | W:1 | W:7 | W:3 | W:7 |
+/// |_____|_____|_____|_____|
+/// ```
+pub(crate) fn grid_nodes() -> HashMap<i32, (Vec<(i32, f32)>, f32)> {
+	let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+	nodes.insert(0, (vec![(4, 1.0), (1, 1.0)], 1.0));
+	nodes.insert(1, (vec![(5, 1.0), (2, 1.0), (0, 1.0)], 7.0));
+	nodes.insert(2, (vec![(6, 1.0), (3, 1.0), (1, 1.0)], 3.0));
+	nodes.insert(3, (vec![(7, 1.0), (2, 1.0)], 7.0));
+	nodes.insert(4, (vec![(8, 1.0), (5, 1.0), (0, 1.0)], 1.0));
+	nodes.insert(5, (vec![(9, 1.0), (6, 1.0), (1, 1.0), (4, 1.0)], 9.0));
+	nodes.insert(6, (vec![(10, 1.0), (7, 1.0), (2, 1.0), (5, 1.0)], 14.0));
+	nodes.insert(7, (vec![(11, 1.0), (3, 1.0), (6, 1.0)], 6.0));
+	nodes.insert(8, (vec![(12, 1.0), (9, 1.0), (4, 1.0)], 1.0));
+	nodes.insert(9, (vec![(13, 1.0), (10, 1.0), (5, 1.0), (8, 1.0)], 1.0));
+	nodes.insert(10, (vec![(14, 1.0), (11, 1.0), (6, 1.0), (9, 1.0)], 4.0));
+	nodes.insert(11, (vec![(15, 1.0), (7, 1.0), (10, 1.0)], 3.0));
+	nodes.insert(12, (vec![(13, 1.0), (8, 1.0)], 5.0));
+	nodes.insert(13, (vec![(14, 1.0), (9, 1.0), (12, 1.0)], 8.0));
+	nodes.insert(14, (vec![(15, 1.0), (10, 1.0), (13, 1.0)], 9.0));
+	nodes.insert(15, (vec![(11, 1.0), (14, 1.0)], 4.0));
+	nodes
+}
+
+/// Builds a square grid of `size` by `size` nodes with unit distances and labels
+/// of `x + y * size`, weights vary by position to give the search some work to do
+pub(crate) fn large_grid_nodes(size: i32) -> HashMap<i32, (Vec<(i32, f32)>, f32)> {
+	let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+	for y in 0..size {
+		for x in 0..size {
+			let mut neighbours = Vec::new();
+			if x + 1 < size {
+				neighbours.push((x + 1 + y * size, 1.0));
+			}
+			if y + 1 < size {
+				neighbours.push((x + (y + 1) * size, 1.0));
+			}
+			if x > 0 {
+				neighbours.push((x - 1 + y * size, 1.0));
+			}
+			if y > 0 {
+				neighbours.push((x + (y - 1) * size, 1.0));
+			}
+			let weight = ((x * 7 + y * 13) % 5) as f32;
+			nodes.insert(x + y * size, (neighbours, weight));
+		}
+	}
+	nodes
+}
+
+/// Builds the hexagonal grid from the `astar_hexagon_up_right` test, a unit distance of
+/// 1.0 separates each hexagon:
+///```txt
+///                 _________               _________
+///                /         \             /         \
+///               /           \           /           \
+///     _________/    (1,3)    \_________/    (3,3)    \
+///    /         \             /         \             /
+///   /           \    W:2    /           \    W:2    /
+///  /    (0,3)    \_________/    (2,3)    \_________/
+///  \             /         \             /         \
+///   \    W:3    /           \    W:9    /           \
+///    \_________/    (1,2)    \_________/    (3,2)    \
+///    /         \             /         \             /
+///   /           \    W:4    /           \    W:5    /
+///  /    (0,2)    \_________/    (2,2)    \_________/
+///  \             /         \             /         \
+///   \    W:1    /           \    W:8    /           \
+///    \_________/    (1,1)    \_________/    (3,1)    \
+///    /         \             /         \             /
+///   /           \    W:9    /           \    W:4    /
+///  /    (0,1)    \_________/    (2,1)    \_________/
+///  \             /         \             /         \
+///   \    W:1    /           \    W:6    /           \
+///    \_________/    (1,0)    \_________/    (3,0)    \
+///    /         \             /         \             /
+///   /           \    W:2    /           \    W:3    /
+///  /    (0,0)    \_________/    (2,0)    \_________/
+///  \             /         \             /
+///   \    W:1    /           \    W:2    /
+///    \_________/             \_________/
+///  ```
+#[allow(clippy::type_complexity)]
+pub(crate) fn hexagon_nodes() -> HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> {
+	let mut nodes: HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> = HashMap::new();
+	nodes.insert((0, 0), (vec![((0, 1), 1.0), ((1, 0), 1.0)], 1.0));
+	nodes.insert(
+		(0, 1),
+		(
+			vec![((0, 2), 1.0), ((1, 1), 1.0), ((1, 0), 1.0), ((0, 0), 1.0)],
+			1.0,
+		),
+	);
+	nodes.insert(
+		(0, 2),
+		(
+			vec![((0, 3), 1.0), ((1, 2), 1.0), ((1, 1), 1.0), ((0, 1), 1.0)],
+			1.0,
+		),
+	);
+	nodes.insert(
+		(0, 3),
+		(vec![((1, 3), 1.0), ((1, 2), 1.0), ((0, 2), 1.0)], 3.0),
+	);
+	nodes.insert(
+		(1, 0),
+		(
+			vec![
+				((1, 1), 1.0),
+				((2, 1), 1.0),
+				((2, 0), 1.0),
+				((0, 0), 1.0),
+				((0, 1), 1.0),
+			],
+			2.0,
+		),
+	);
+	nodes.insert(
+		(1, 1),
+		(
+			vec![
+				((1, 2), 1.0),
+				((2, 2), 1.0),
+				((2, 1), 1.0),
+				((1, 0), 1.0),
+				((0, 1), 1.0),
+				((0, 2), 1.0),
+			],
+			9.0,
+		),
+	);
+	nodes.insert(
+		(1, 2),
+		(
+			vec![
+				((1, 3), 1.0),
+				((2, 3), 1.0),
+				((2, 2), 1.0),
+				((1, 1), 1.0),
+				((0, 2), 1.0),
+				((0, 3), 1.0),
+			],
+			4.0,
+		),
+	);
+	nodes.insert(
+		(1, 3),
+		(vec![((2, 3), 1.0), ((1, 2), 1.0), ((0, 3), 1.0)], 2.0),
+	);
+	nodes.insert(
+		(2, 0),
+		(vec![((2, 1), 1.0), ((3, 0), 1.0), ((1, 0), 1.0)], 2.0),
+	);
+	nodes.insert(
+		(2, 1),
+		(
+			vec![
+				((2, 2), 1.0),
+				((3, 1), 1.0),
+				((3, 0), 1.0),
+				((2, 0), 1.0),
+				((1, 0), 1.0),
+				((1, 1), 1.0),
+			],
+			6.0,
+		),
+	);
+	nodes.insert(
+		(2, 2),
+		(
+			vec![
+				((2, 3), 1.0),
+				((3, 2), 1.0),
+				((3, 1), 1.0),
+				((2, 1), 1.0),
+				((1, 1), 1.0),
+				((1, 2), 1.0),
+			],
+			8.0,
+		),
+	);
+	nodes.insert(
+		(2, 3),
+		(
+			vec![
+				((3, 3), 1.0),
+				((3, 2), 1.0),
+				((2, 2), 1.0),
+				((1, 2), 1.0),
+				((1, 3), 1.0),
+			],
+			9.0,
+		),
+	);
+	nodes.insert(
+		(3, 0),
+		(vec![((3, 1), 1.0), ((2, 0), 1.0), ((2, 1), 1.0)], 3.0),
+	);
+	nodes.insert(
+		(3, 1),
+		(
+			vec![((3, 2), 1.0), ((3, 0), 1.0), ((2, 1), 1.0), ((2, 2), 1.0)],
+			4.0,
+		),
+	);
+	nodes.insert(
+		(3, 2),
+		(
+			vec![((3, 3), 1.0), ((3, 1), 1.0), ((2, 2), 1.0), ((2, 3), 1.0)],
+			5.0,
+		),
+	);
+	nodes.insert((3, 3), (vec![((3, 2), 1.0), ((2, 3), 1.0)], 2.0));
+	nodes
+}
+
+/// Builds a graph where no path exists from node `0` to node `5`, while `5` has an edge
+/// backwards to `4` nothing leads to `5` in the first place
+pub(crate) fn no_path_nodes() -> HashMap<i32, (Vec<(i32, f32)>, f32)> {
+	let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+	nodes.insert(0, (vec![(1, 5.0)], 3.0));
+	nodes.insert(1, (vec![(0, 5.0), (2, 3.0)], 2.0));
+	nodes.insert(2, (vec![(1, 3.0), (3, 2.0), (4, 1.0)], 1.0));
+	nodes.insert(3, (vec![(2, 2.0)], 5.0));
+	nodes.insert(4, (vec![(2, 1.0)], 2.0));
+	nodes.insert(5, (vec![(4, 3.0)], 6.0));
+	nodes
+}
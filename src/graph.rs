@@ -0,0 +1,843 @@
+//! Helpers for constructing the `nodes` data set consumed by the pathfinding functions
+//!
+
+use std::{cmp::Ordering, collections::HashMap, hash::Hash};
+
+use crate::error::BuildError;
+
+/// Assembles a `nodes` data set from a flat list of `(from, to, distance)` edges and a
+/// list of `(node, weight)` pairs, matching how many CSV/TSV datasets are shaped.
+///
+/// Edges are directional, to allow travel both ways include an edge for each direction.
+/// Every node mentioned by either list becomes a key of the returned map, neighbours
+/// keep the order in which their edges appear and any node found in `edges` but missing
+/// from `weights` is given a default weight of `0.0`. A node listed in `weights` more than
+/// once takes its last weight, use [try_from_edge_list] to have conflicting weights
+/// reported instead.
+///
+/// ```rust
+/// use pathfinding_astar::{astar_path, from_edge_list};
+///
+/// let edges = [(0, 1, 5.0), (1, 2, 4.0)];
+/// let weights = [(0, 3.0), (1, 2.0), (2, 6.0)];
+/// let nodes = from_edge_list(&edges, &weights);
+/// let path = astar_path(0, &nodes, 2).unwrap();
+/// assert_eq!(vec![0, 1, 2], path);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn from_edge_list<T>(
+	edges: &[(T, T, f32)],
+	weights: &[(T, f32)],
+) -> HashMap<T, (Vec<(T, f32)>, f32)>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	let mut nodes: HashMap<T, (Vec<(T, f32)>, f32)> = HashMap::new();
+	for (node, weight) in weights.iter() {
+		nodes.entry(*node).or_insert_with(|| (Vec::new(), 0.0)).1 = *weight;
+	}
+	for (from, to, distance) in edges.iter() {
+		nodes
+			.entry(*from)
+			.or_insert_with(|| (Vec::new(), 0.0))
+			.0
+			.push((*to, *distance));
+		// ensure sinks referenced only as a neighbour still exist as a key
+		nodes.entry(*to).or_insert_with(|| (Vec::new(), 0.0));
+	}
+	nodes
+}
+
+/// Behaves like [from_edge_list] but fails with [BuildError::ConflictingWeight] if a node is
+/// listed in `weights` more than once with different weights, rather than silently keeping
+/// the last.
+///
+/// ```rust
+/// use pathfinding_astar::{try_from_edge_list, BuildError};
+///
+/// let edges = [(0, 1, 5.0)];
+/// let weights = [(0, 1.0), (1, 2.0), (0, 3.0)];
+/// let conflict = BuildError::ConflictingWeight {
+///     node: 0,
+///     first: 1.0,
+///     second: 3.0,
+/// };
+/// assert_eq!(Err(conflict), try_from_edge_list(&edges, &weights));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn try_from_edge_list<T>(
+	edges: &[(T, T, f32)],
+	weights: &[(T, f32)],
+) -> Result<HashMap<T, (Vec<(T, f32)>, f32)>, BuildError<T>>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	let mut seen: HashMap<T, f32> = HashMap::with_capacity(weights.len());
+	for (node, weight) in weights.iter() {
+		match seen.get(node) {
+			Some(first) if first != weight => {
+				return Err(BuildError::ConflictingWeight {
+					node: *node,
+					first: *first,
+					second: *weight,
+				});
+			}
+			Some(_) => {}
+			None => {
+				seen.insert(*node, *weight);
+			}
+		}
+	}
+	Ok(from_edge_list(edges, weights))
+}
+
+/// Builds a `width` by `height` 4-connected grid with pseudo-random weights derived from
+/// `seed`, useful for standardising benchmarks and stress tests.
+///
+/// Nodes are labelled `(x, y)` with `0 <= x < width` and `0 <= y < height`, each is
+/// connected to its orthogonal neighbours with a distance of `1.0` and given a weight in
+/// the range `[0.0, 10.0)`. The same `seed` always produces the same grid.
+///
+/// ```rust
+/// use pathfinding_astar::{astar_path, generate_grid};
+///
+/// let nodes = generate_grid(8, 8, 42);
+/// assert_eq!(64, nodes.len());
+/// let path = astar_path((0, 0), &nodes, (7, 7)).unwrap();
+/// assert_eq!(Some(&(7, 7)), path.last());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn generate_grid(
+	width: usize,
+	height: usize,
+	seed: u64,
+) -> HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> {
+	let mut rng = SplitMix64(seed);
+	let (w, h) = (width as i32, height as i32);
+	let mut nodes = HashMap::with_capacity(width * height);
+	for y in 0..h {
+		for x in 0..w {
+			let mut neighbours = Vec::with_capacity(4);
+			let candidates = [(x + 1, y), (x, y + 1), (x - 1, y), (x, y - 1)];
+			for (nx, ny) in candidates.iter() {
+				if (0..w).contains(nx) && (0..h).contains(ny) {
+					neighbours.push(((*nx, *ny), 1.0));
+				}
+			}
+			let weight = rng.next_f32() * 10.0;
+			nodes.insert((x, y), (neighbours, weight));
+		}
+	}
+	nodes
+}
+
+/// Which neighbouring cells of a grid a cell is connected to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+	/// The orthogonal neighbours, each at a distance of `1.0`
+	Four,
+	/// The orthogonal neighbours plus the diagonal neighbours, each diagonal at a distance
+	/// of `√2`
+	Eight,
+}
+
+/// Builds a grid from tile weights stored in row-major order, as many engines store
+/// tilemaps, where the cell at column `x` and row `y` has the index `y * width + x`.
+///
+/// Nodes are labelled by their index into `weights` and take the weight stored there.
+/// Orthogonal neighbours are at a distance of `1.0`, with [Connectivity::Eight] diagonal
+/// neighbours are also connected at a distance of `√2`.
+///
+/// ```rust
+/// use pathfinding_astar::{astar_path, flat_grid_graph, Connectivity};
+///
+/// let weights = vec![1.0, 1.0, 1.0, 9.0, 9.0, 1.0];
+/// let nodes = flat_grid_graph(&weights, 3, Connectivity::Four);
+/// assert_eq!(6, nodes.len());
+/// assert_eq!(Some(vec![0, 1, 2, 5]), astar_path(0, &nodes, 5));
+/// ```
+///
+/// # Panics
+///
+/// If `width` is zero or the length of `weights` isn't a multiple of `width`
+#[allow(clippy::type_complexity)]
+pub fn flat_grid_graph(
+	weights: &[f32],
+	width: usize,
+	connectivity: Connectivity,
+) -> HashMap<usize, (Vec<(usize, f32)>, f32)> {
+	assert!(width > 0, "Grid width must be greater than zero");
+	assert!(
+		weights.len() % width == 0,
+		"Grid of {} weights can't be divided into rows of width {}",
+		weights.len(),
+		width
+	);
+	let (w, h) = (width as i64, (weights.len() / width) as i64);
+	let orthogonal = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+	let diagonal = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+	let mut nodes = HashMap::with_capacity(weights.len());
+	for (index, weight) in weights.iter().enumerate() {
+		let (x, y) = (index as i64 % w, index as i64 / w);
+		let mut neighbours = Vec::new();
+		let mut connect = |offsets: &[(i64, i64)], distance: f32| {
+			for (dx, dy) in offsets.iter() {
+				let (nx, ny) = (x + dx, y + dy);
+				if (0..w).contains(&nx) && (0..h).contains(&ny) {
+					neighbours.push(((ny * w + nx) as usize, distance));
+				}
+			}
+		};
+		connect(&orthogonal, 1.0);
+		if connectivity == Connectivity::Eight {
+			connect(&diagonal, std::f32::consts::SQRT_2);
+		}
+		nodes.insert(index, (neighbours, *weight));
+	}
+	nodes
+}
+
+/// Finds the node of a grid labelled by `(x, y)` cell coordinates, such as one built by
+/// [generate_grid], closest to `point` in Euclidean distance. Useful for snapping a start or
+/// end given in world coordinates onto the graph before searching it.
+///
+/// Where several nodes are equally close, such as a point exactly midway between cells, the
+/// node with the lowest `x` and then the lowest `y` is chosen. Returns `None` if `nodes` is
+/// empty.
+///
+/// ```rust
+/// use pathfinding_astar::{generate_grid, nearest_node};
+///
+/// let nodes = generate_grid(4, 4, 7);
+/// assert_eq!(Some((2, 1)), nearest_node((1.8, 0.9), &nodes));
+/// assert_eq!(Some((3, 3)), nearest_node((10.0, 10.0), &nodes));
+/// // midway between (1, 1), (2, 1), (1, 2) and (2, 2)
+/// assert_eq!(Some((1, 1)), nearest_node((1.5, 1.5), &nodes));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn nearest_node(
+	point: (f32, f32),
+	nodes: &HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)>,
+) -> Option<(i32, i32)> {
+	let squared_distance = |(x, y): (i32, i32)| {
+		let (dx, dy) = (x as f32 - point.0, y as f32 - point.1);
+		dx * dx + dy * dy
+	};
+	nodes
+		.keys()
+		.map(|node| (squared_distance(*node), *node))
+		.min_by(|a, b| {
+			a.0.partial_cmp(&b.0)
+				.unwrap_or(Ordering::Equal)
+				.then_with(|| a.1.cmp(&b.1))
+		})
+		.map(|(_, node)| node)
+}
+
+/// The Chebyshev distance between two cells of a grid, the number of moves between them
+/// where a diagonal move costs the same as a straight one. Useful as the heuristic of an
+/// 8-connected grid whose diagonals have a distance of `1.0`.
+///
+/// ```rust
+/// use pathfinding_astar::chebyshev;
+///
+/// assert_eq!(3.0, chebyshev((0, 0), (3, 1)));
+/// assert_eq!(4.0, chebyshev((2, -1), (-1, 3)));
+/// ```
+pub fn chebyshev(a: (i32, i32), b: (i32, i32)) -> f32 {
+	let (dx, dy) = ((a.0 - b.0).abs(), (a.1 - b.1).abs());
+	dx.max(dy) as f32
+}
+
+/// The octile distance between two cells of a grid, the length of the shortest route
+/// between them made of straight moves of distance `1.0` and diagonal moves of distance
+/// `√2`. This is the admissible heuristic of an 8-connected grid such as one built by
+/// [flat_grid_graph] with [Connectivity::Eight].
+///
+/// ```rust
+/// use pathfinding_astar::octile;
+///
+/// // one diagonal move and two straight moves
+/// assert_eq!(2.0 + std::f32::consts::SQRT_2, octile((0, 0), (3, 1)));
+/// assert_eq!(0.0, octile((5, 5), (5, 5)));
+/// ```
+pub fn octile(a: (i32, i32), b: (i32, i32)) -> f32 {
+	let (dx, dy) = ((a.0 - b.0).abs(), (a.1 - b.1).abs());
+	let (straight, diagonal) = ((dx - dy).abs(), dx.min(dy));
+	straight as f32 + diagonal as f32 * std::f32::consts::SQRT_2
+}
+
+/// The step taken between each consecutive pair of cells of a path over a grid labelled
+/// by `(x, y)` cell coordinates, such as `(1, 0)` for a move along `x` and `(1, 1)` for a
+/// diagonal move. Useful for choosing the sprite or animation of each move.
+///
+/// The result has one fewer entry than `path`, so a path of fewer than two cells has no
+/// steps.
+///
+/// ```rust
+/// use pathfinding_astar::grid_path_directions;
+///
+/// let path = [(0, 0), (1, 0), (2, 1), (2, 2)];
+/// assert_eq!(vec![(1, 0), (1, 1), (0, 1)], grid_path_directions(&path));
+/// assert!(grid_path_directions(&[(4, 4)]).is_empty());
+/// ```
+pub fn grid_path_directions(path: &[(i32, i32)]) -> Vec<(i32, i32)> {
+	path.windows(2)
+		.map(|pair| (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1))
+		.collect()
+}
+
+/// Combines two `nodes` data sets into one, such as when stitching together tiles or
+/// chunks of a world as they load.
+///
+/// Every node of `a` and `b` is kept. Where a node is present in both its neighbours are
+/// merged, keeping the edges of `a` first followed by any edges of `b` which aren't already
+/// present with an identical distance, and the lower of the two weights is used.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path, merge_graphs};
+///
+/// let mut a: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// a.insert(0, (vec![(1, 1.0)], 1.0));
+/// a.insert(1, (vec![], 4.0));
+/// let mut b: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// b.insert(1, (vec![(2, 1.0)], 2.0));
+/// b.insert(2, (vec![], 1.0));
+/// let merged = merge_graphs(&a, &b);
+/// assert_eq!(Some(&(vec![(2, 1.0)], 2.0)), merged.get(&1));
+/// assert_eq!(Some(vec![0, 1, 2]), astar_path(0, &merged, 2));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn merge_graphs<T>(
+	a: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	b: &HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> HashMap<T, (Vec<(T, f32)>, f32)>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	let mut merged = a.clone();
+	for (node, (neighbours, weight)) in b.iter() {
+		match merged.get_mut(node) {
+			Some((existing_neighbours, existing_weight)) => {
+				for edge in neighbours.iter() {
+					if !existing_neighbours.contains(edge) {
+						existing_neighbours.push(*edge);
+					}
+				}
+				*existing_weight = existing_weight.min(*weight);
+			}
+			None => {
+				merged.insert(*node, (neighbours.clone(), *weight));
+			}
+		}
+	}
+	merged
+}
+
+/// Rewrites every node label of `nodes`, both keys and neighbours, through `f`, such as when
+/// migrating from `(i32, i32)` labels to a custom point type.
+///
+/// Weights, distances and the order of neighbours are kept. `f` should give each node a
+/// distinct label, where two nodes are given the same label only one of them is kept.
+/// The new labels may borrow from the old, such as `&str` labels from `String`s.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path, map_labels};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 1.0));
+/// nodes.insert(1, (vec![], 1.0));
+/// let points = map_labels(&nodes, |n| (*n, 0));
+/// assert_eq!(Some(vec![(0, 0), (1, 0)]), astar_path((0, 0), &points, (1, 0)));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn map_labels<'a, T, U, F>(
+	nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>,
+	f: F,
+) -> HashMap<U, (Vec<(U, f32)>, f32)>
+where
+	U: Eq + Hash,
+	F: Fn(&'a T) -> U,
+{
+	nodes
+		.iter()
+		.map(|(node, (neighbours, weight))| {
+			let neighbours = neighbours
+				.iter()
+				.map(|(neighbour, distance)| (f(neighbour), *distance))
+				.collect();
+			(f(node), (neighbours, *weight))
+		})
+		.collect()
+}
+
+/// Converts weights which measure how desirable a node is, where larger is preferred, into
+/// the weights used by this crate, where larger is harder, by mapping each weight `w` to
+/// `max - w`. Neighbours and distances are kept.
+///
+/// `max` should be at least the largest weight of `nodes`, as a weight above it becomes
+/// negative and a search over negative weights may not return the best path.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::invert_weights;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 2.0));
+/// nodes.insert(1, (vec![], 10.0));
+/// let inverted = invert_weights(&nodes, 10.0);
+/// assert_eq!(Some(&(vec![(1, 1.0)], 8.0)), inverted.get(&0));
+/// assert_eq!(Some(&(vec![], 0.0)), inverted.get(&1));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn invert_weights<T>(
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	max: f32,
+) -> HashMap<T, (Vec<(T, f32)>, f32)>
+where
+	T: Eq + Hash + Clone,
+{
+	nodes
+		.iter()
+		.map(|(node, (neighbours, weight))| (node.clone(), (neighbours.clone(), max - weight)))
+		.collect()
+}
+
+/// Collapses `nodes` into super-nodes, one for each distinct group given by `group_of`,
+/// producing a coarse graph to search first for hierarchical pathfinding before refining
+/// the route within each group.
+///
+/// Each super-node takes the average weight of the nodes in its group. Edges between nodes
+/// of the same group are dropped while those leading from one group into another become a
+/// single edge between the two super-nodes, whose distance is the shortest of the edges
+/// it replaces. The shortest crossing keeps the coarse distance from overestimating the
+/// cost of moving between groups. Neighbours are listed in no particular order.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::coarsen;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 2.0));
+/// nodes.insert(1, (vec![(2, 3.0), (3, 2.0)], 4.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// let coarse = coarsen(&nodes, |n| n / 2);
+/// assert_eq!(Some(&(vec![(1, 2.0)], 3.0)), coarse.get(&0));
+/// assert_eq!(Some(&(vec![], 1.0)), coarse.get(&1));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn coarsen<T, G, F>(
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	group_of: F,
+) -> HashMap<G, (Vec<(G, f32)>, f32)>
+where
+	T: Eq + Hash + Copy,
+	G: Eq + Hash + Copy,
+	F: Fn(&T) -> G,
+{
+	// total weight and number of members of each group
+	let mut members: HashMap<G, (f32, usize)> = HashMap::new();
+	// shortest distance of the edges crossing from one group to another
+	let mut crossings: HashMap<G, HashMap<G, f32>> = HashMap::new();
+	for (node, (neighbours, weight)) in nodes.iter() {
+		let group = group_of(node);
+		let entry = members.entry(group).or_insert((0.0, 0));
+		entry.0 += weight;
+		entry.1 += 1;
+		for (neighbour, distance) in neighbours.iter() {
+			let neighbour_group = group_of(neighbour);
+			if neighbour_group == group {
+				continue;
+			}
+			let shortest = crossings
+				.entry(group)
+				.or_default()
+				.entry(neighbour_group)
+				.or_insert(*distance);
+			*shortest = shortest.min(*distance);
+		}
+	}
+	members
+		.into_iter()
+		.map(|(group, (total_weight, count))| {
+			let neighbours = crossings
+				.remove(&group)
+				.map(|edges| edges.into_iter().collect())
+				.unwrap_or_default();
+			(group, (neighbours, total_weight / count as f32))
+		})
+		.collect()
+}
+
+/// Reverses every edge of `nodes`, giving for each node the `(node, distance)` pairs of the
+/// edges which lead into it, so an edge from `a` to `b` of distance `d` becomes an entry
+/// `(a, d)` for `b`. Every key of `nodes` is present, along with any node only referenced
+/// as a neighbour.
+///
+/// Searches which travel backwards from a goal need this map, building it once and handing
+/// it to variants such as [dijkstra_bidirectional_with_reverse](crate::dijkstra_bidirectional_with_reverse)
+/// avoids rebuilding it for every query.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::build_reverse;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![], 2.0));
+/// let reverse = build_reverse(&nodes);
+/// assert_eq!(Some(&vec![(0, 5.0)]), reverse.get(&1));
+/// assert_eq!(Some(&vec![]), reverse.get(&0));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn build_reverse<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> HashMap<T, Vec<(T, f32)>>
+where
+	T: Eq + Hash + Copy,
+{
+	let mut reverse: HashMap<T, Vec<(T, f32)>> =
+		nodes.keys().map(|node| (*node, Vec::new())).collect();
+	for (from, to, distance) in edges(nodes) {
+		reverse.entry(to).or_default().push((from, distance));
+	}
+	reverse
+}
+
+/// Iterates every edge of `nodes` as a `(from, to, distance)` triple, for analysis which
+/// treats all edges alike rather than walking the neighbours of each node.
+///
+/// Edges leaving the same node keep the order of its neighbours, the nodes themselves are
+/// visited in no particular order.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::edges;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0), (2, 1.0)], 3.0));
+/// nodes.insert(1, (vec![], 2.0));
+/// nodes.insert(2, (vec![(1, 1.0)], 2.0));
+/// let total: f32 = edges(&nodes).map(|(_, _, distance)| distance).sum();
+/// assert_eq!(7.0, total);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn edges<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> impl Iterator<Item = (T, T, f32)> + '_
+where
+	T: Copy,
+{
+	nodes.iter().flat_map(|(from, (neighbours, _))| {
+		neighbours
+			.iter()
+			.map(move |(to, distance)| (*from, *to, *distance))
+	})
+}
+
+/// The distance of the edge from `from` to `to`, where several edges join them the
+/// shortest is used as it is the one a search would travel along. `None` if no edge exists
+#[allow(clippy::type_complexity)]
+pub(crate) fn edge_distance<T>(
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	from: &T,
+	to: &T,
+) -> Option<f32>
+where
+	T: Eq + Hash,
+{
+	nodes
+		.get(from)?
+		.0
+		.iter()
+		.filter(|(neighbour, _)| neighbour == to)
+		.map(|(_, distance)| *distance)
+		.reduce(f32::min)
+}
+
+/// Minimal SplitMix64 pseudo-random number generator, deterministic for a given seed
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+	/// Produces the next pseudo-random `u64` in the sequence
+	fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+	/// Produces the next pseudo-random `f32` in the range `[0.0, 1.0)`
+	fn next_f32(&mut self) -> f32 {
+		// use the top 24 bits so every value is exactly representable
+		(self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fixtures::grid_nodes;
+	use crate::{astar_path, check_heuristic_consistency};
+
+	#[test]
+	/// The octile distance of `(3, 1)` is one diagonal and two straight moves, and along
+	/// with the Chebyshev distance never overestimates the distances of an 8-connected grid
+	fn octile_and_chebyshev_admissible() {
+		assert_eq!(2.0 + std::f32::consts::SQRT_2, octile((0, 0), (3, 1)));
+		assert_eq!(octile((0, 0), (3, 1)), octile((3, 1), (0, 0)));
+		assert_eq!(3.0, chebyshev((0, 0), (3, 1)));
+		let width = 5;
+		let nodes = flat_grid_graph(&[0.0; 25], width, Connectivity::Eight);
+		let cell = |n: &usize| ((n % width) as i32, (n / width) as i32);
+		for end in [0, 12, 19].iter() {
+			let by_octile = |a: &usize, b: &usize| octile(cell(a), cell(b));
+			assert!(check_heuristic_consistency(&nodes, by_octile, *end).is_empty());
+			let by_chebyshev = |a: &usize, b: &usize| chebyshev(cell(a), cell(b));
+			assert!(check_heuristic_consistency(&nodes, by_chebyshev, *end).is_empty());
+		}
+	}
+	#[test]
+	/// The path across a grid with a single costly cell in the way steps around it, with a
+	/// diagonal move where the detour allows
+	fn directions_of_grid_path() {
+		let width = 3;
+		#[rustfmt::skip]
+		let weights = [
+			0.0, 0.0, 0.0,
+			0.0, 9.0, 0.0,
+			0.0, 0.0, 0.0,
+		];
+		let nodes = flat_grid_graph(&weights, width, Connectivity::Eight);
+		let path: Vec<(i32, i32)> = astar_path(0, &nodes, 7)
+			.unwrap()
+			.iter()
+			.map(|n| ((n % width) as i32, (n / width) as i32))
+			.collect();
+		assert_eq!(vec![(0, 0), (0, 1), (1, 2)], path);
+		assert_eq!(vec![(0, 1), (1, 1)], grid_path_directions(&path));
+		assert!(grid_path_directions(&[]).is_empty());
+	}
+	#[test]
+	/// Once inverted the most desirable nodes, formerly avoided as the heaviest, are the
+	/// route taken
+	fn inverted_weights_prefer_desirable_route() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 0.0));
+		nodes.insert(1, (vec![(3, 1.0)], 9.0));
+		nodes.insert(2, (vec![(3, 1.0)], 1.0));
+		nodes.insert(3, (vec![], 5.0));
+		assert_eq!(Some(vec![0, 2, 3]), astar_path(0, &nodes, 3));
+		let inverted = invert_weights(&nodes, 9.0);
+		assert_eq!(Some(vec![0, 1, 3]), astar_path(0, &inverted, 3));
+		assert!(inverted.values().all(|(_, weight)| *weight >= 0.0));
+	}
+	#[test]
+	/// A point among four cells snaps to the closest of them, an exact midpoint takes the
+	/// lowest `x` then `y`
+	fn nearest_node_between_cells() {
+		let nodes = generate_grid(6, 6, 3);
+		assert_eq!(Some((3, 2)), nearest_node((2.6, 2.45), &nodes));
+		assert_eq!(Some((2, 3)), nearest_node((2.4, 2.7), &nodes));
+		assert_eq!(Some((2, 2)), nearest_node((2.5, 2.5), &nodes));
+		assert_eq!(Some((0, 4)), nearest_node((-1.0, 4.5), &nodes));
+		assert_eq!(None, nearest_node((0.0, 0.0), &HashMap::new()));
+	}
+	#[test]
+	/// Builds the graph from the "What Is A-Star?" section of the README from edge-list form
+	/// ```txt
+	///                    Length:22             W:4
+	///         W:1  S ----------------------> O1
+	///             |                         |
+	///             |                         |
+	///    Length:5 |                         | Length:4
+	///             |                         |
+	///             ▼                         ▼
+	///             O2 ---------------------> E
+	///        W:1          Length:20            W:2
+	///  ```
+	#[allow(clippy::type_complexity)]
+	fn readme_graph_from_edge_list() {
+		let edges = [
+			((0, 0), (0, 1), 22.0),
+			((0, 0), (0, 2), 5.0),
+			((0, 1), (0, 3), 4.0),
+			((0, 2), (0, 3), 20.0),
+		];
+		let weights = [((0, 0), 1.0), ((0, 1), 4.0), ((0, 2), 1.0), ((0, 3), 2.0)];
+		let nodes = from_edge_list(&edges, &weights);
+
+		let mut expected: HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> = HashMap::new();
+		expected.insert((0, 0), (vec![((0, 1), 22.0), ((0, 2), 5.0)], 1.0));
+		expected.insert((0, 1), (vec![((0, 3), 4.0)], 4.0));
+		expected.insert((0, 2), (vec![((0, 3), 20.0)], 1.0));
+		expected.insert((0, 3), (vec![], 2.0));
+		assert_eq!(expected, nodes);
+
+		let path = astar_path((0, 0), &nodes, (0, 3)).unwrap();
+		assert_eq!(vec![(0, 0), (0, 2), (0, 3)], path);
+	}
+	#[test]
+	/// Nodes which only appear in the edge list receive a default weight of `0.0`
+	fn edge_list_default_weight() {
+		let edges = [(0, 1, 2.0)];
+		let weights = [(0, 3.0)];
+		let nodes = from_edge_list(&edges, &weights);
+		assert_eq!(Some(&(vec![], 0.0)), nodes.get(&1));
+		assert_eq!(Some(&(vec![(1, 2.0)], 3.0)), nodes.get(&0));
+	}
+	#[test]
+	/// Node `0` given a weight of `1.0` and then `3.0` is reported as a conflict, repeating
+	/// the same weight is accepted
+	fn edge_list_conflicting_weight() {
+		let edges = [(0, 1, 2.0)];
+		assert_eq!(
+			Err(BuildError::ConflictingWeight {
+				node: 0,
+				first: 1.0,
+				second: 3.0
+			}),
+			try_from_edge_list(&edges, &[(0, 1.0), (0, 3.0)])
+		);
+		let nodes = try_from_edge_list(&edges, &[(0, 1.0), (0, 1.0)]).unwrap();
+		assert_eq!(from_edge_list(&edges, &[(0, 1.0)]), nodes);
+	}
+	#[test]
+	/// The same seed produces identical grids while a different seed changes the weights
+	fn generate_grid_deterministic() {
+		let a = generate_grid(16, 12, 7);
+		let b = generate_grid(16, 12, 7);
+		let c = generate_grid(16, 12, 8);
+		assert_eq!(16 * 12, a.len());
+		assert_eq!(a, b);
+		assert_ne!(a, c);
+		// corners have two neighbours and every weight is in range
+		assert_eq!(2, a[&(0, 0)].0.len());
+		assert_eq!(4, a[&(5, 5)].0.len());
+		assert!(a.values().all(|(_, w)| (0.0..10.0).contains(w)));
+	}
+	#[test]
+	/// Every edge of the README graph and grid is visited exactly once
+	fn readme_edge_count() {
+		let edges_list = [(0, 1, 22.0), (0, 2, 5.0), (1, 3, 4.0), (2, 3, 20.0)];
+		let nodes = from_edge_list(&edges_list, &[]);
+		let mut found: Vec<(i32, i32, f32)> = edges(&nodes).collect();
+		found.sort_by_key(|(from, to, _)| (*from, *to));
+		assert_eq!(edges_list.to_vec(), found);
+		// a 4x4 grid has 24 adjacent pairs each joined in both directions
+		assert_eq!(48, edges(&grid_nodes()).count());
+	}
+	#[test]
+	/// The README grid grouped into its four 2x2 quadrants gives a coarse grid of four
+	/// super-nodes, each joined to its two orthogonally adjacent quadrants
+	#[allow(clippy::type_complexity)]
+	fn coarsen_grid_into_quadrants() {
+		let coarse = coarsen(&grid_nodes(), |n| (n % 4 / 2, n / 4 / 2));
+		assert_eq!(4, coarse.len());
+		let mut neighbours = coarse[&(0, 0)].0.clone();
+		neighbours.sort_by_key(|(quadrant, _)| *quadrant);
+		assert_eq!(vec![((0, 1), 1.0), ((1, 0), 1.0)], neighbours);
+		assert!(coarse.values().all(|(edges, _)| edges.len() == 2));
+		// averages of the weights in each quadrant
+		assert_eq!(4.5, coarse[&(0, 0)].1);
+		assert_eq!(7.5, coarse[&(1, 0)].1);
+		assert_eq!(3.75, coarse[&(0, 1)].1);
+		assert_eq!(5.0, coarse[&(1, 1)].1);
+		assert_eq!(
+			Some(vec![(0, 0), (0, 1), (1, 1)]),
+			astar_path((0, 0), &coarse, (1, 1))
+		);
+	}
+	#[test]
+	/// A 3x3 flat grid links corner to corner along its edges with 4-connectivity and
+	/// straight through the centre with 8-connectivity
+	fn flat_grid_corner_to_corner() {
+		let weights = [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+		let four = flat_grid_graph(&weights, 3, Connectivity::Four);
+		assert_eq!(9, four.len());
+		assert_eq!(4, four[&4].0.len());
+		let path = astar_path(0, &four, 8).unwrap();
+		assert_eq!(5, path.len());
+		assert_eq!((Some(&0), Some(&8)), (path.first(), path.last()));
+		let eight = flat_grid_graph(&weights, 3, Connectivity::Eight);
+		assert_eq!(8, eight[&4].0.len());
+		assert_eq!(3, eight[&0].0.len());
+		assert_eq!(Some(vec![0, 4, 8]), astar_path(0, &eight, 8));
+	}
+	/// Builds a 2x2 4-connected grid of unit distances with its bottom left corner at
+	/// `(x, y)`, each node is given `weight`
+	#[allow(clippy::type_complexity)]
+	fn square(x: i32, y: i32, weight: f32) -> HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> {
+		let mut nodes = HashMap::new();
+		for (nx, ny) in [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)].iter() {
+			let horizontal = if *nx == x { nx + 1 } else { x };
+			let vertical = if *ny == y { ny + 1 } else { y };
+			nodes.insert(
+				(*nx, *ny),
+				(
+					vec![((horizontal, *ny), 1.0), ((*nx, vertical), 1.0)],
+					weight,
+				),
+			);
+		}
+		nodes
+	}
+	#[test]
+	/// Two 2x2 grids overlapping along the `x = 1` column merge into a 3x2 grid which can be
+	/// crossed from one side of the seam to the other
+	fn merge_overlapping_grids() {
+		let a = square(0, 0, 1.0);
+		let b = square(1, 0, 2.0);
+		let merged = merge_graphs(&a, &b);
+		assert_eq!(6, merged.len());
+		// the seam has edges from both grids, without duplicating the shared vertical edge,
+		// and takes the lower weight
+		assert_eq!(
+			Some(&(vec![((0, 0), 1.0), ((1, 1), 1.0), ((2, 0), 1.0)], 1.0)),
+			merged.get(&(1, 0))
+		);
+		let path = astar_path((0, 0), &merged, (2, 1)).unwrap();
+		assert_eq!(Some(&(0, 0)), path.first());
+		assert_eq!(Some(&(2, 1)), path.last());
+		assert_eq!(4, path.len());
+		assert!(path.iter().any(|(x, _)| *x == 1));
+		// neither grid can make the journey alone
+		assert!(!a.contains_key(&(2, 1)) && !b.contains_key(&(0, 0)));
+	}
+	#[test]
+	/// The grid relabelled with `String`s finds the same path, borrowed as `&str` labels
+	/// to satisfy the `Copy` bound of the search
+	fn map_labels_to_strings() {
+		let nodes = grid_nodes();
+		let strings = map_labels(&nodes, |n| n.to_string());
+		assert_eq!(nodes.len(), strings.len());
+		assert_eq!(
+			Some(&(vec![("4".to_string(), 1.0), ("1".to_string(), 1.0)], 1.0)),
+			strings.get("0")
+		);
+		let borrowed = map_labels(&strings, |n| n.as_str());
+		let path = astar_path("0", &borrowed, "15").unwrap();
+		assert_eq!(vec!["0", "4", "8", "9", "10", "11", "15"], path);
+	}
+	#[test]
+	/// Every edge of the README graph is inverted with its distance kept
+	#[allow(clippy::type_complexity)]
+	fn build_reverse_readme() {
+		let mut nodes: HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> = HashMap::new();
+		nodes.insert((0, 0), (vec![((0, 1), 22.0), ((0, 2), 5.0)], 1.0)); // S
+		nodes.insert((0, 1), (vec![((0, 3), 4.0)], 4.0)); // O1
+		nodes.insert((0, 2), (vec![((0, 3), 20.0)], 1.0)); // O2
+		nodes.insert((0, 3), (vec![], 2.0)); // E
+		let mut reverse = build_reverse(&nodes);
+		for incoming in reverse.values_mut() {
+			incoming.sort_by_key(|(node, _)| *node);
+		}
+		assert_eq!(4, reverse.len());
+		assert_eq!(Some(&vec![]), reverse.get(&(0, 0)));
+		assert_eq!(Some(&vec![((0, 0), 22.0)]), reverse.get(&(0, 1)));
+		assert_eq!(Some(&vec![((0, 0), 5.0)]), reverse.get(&(0, 2)));
+		assert_eq!(
+			Some(&vec![((0, 1), 4.0), ((0, 2), 20.0)]),
+			reverse.get(&(0, 3))
+		);
+	}
+}
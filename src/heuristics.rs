@@ -0,0 +1,494 @@
+//! Searches guided by estimates of the distance remaining to the goal, from none at all,
+//! through a single heuristic, to a blend of several
+//!
+
+use std::{cell::RefCell, collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::{
+	a_star_score, check_endpoints, search_by, stored_neighbours, stored_weight, NoListener,
+	SearchListener, SearchStats, TieBreak, DEFAULT_SCORE_EPSILON,
+};
+
+/// An estimate of the distance remaining from a node to the goal, as taken by
+/// [astar_path_with_heuristic]. Implemented by any `Fn(&T, &T) -> f32` closure, by
+/// [NoHeuristic] and [ZeroHeuristic] and, for heuristics which can rule nodes out, by [Pruning]
+pub trait Heuristic<T> {
+	/// Estimates the distance remaining from `node` to `end_node`, `None` if `node` can
+	/// never lead to `end_node` and so shouldn't be explored
+	fn estimate(&self, node: &T, end_node: &T) -> Option<f32>;
+}
+
+impl<T, F> Heuristic<T> for F
+where
+	F: Fn(&T, &T) -> f32,
+{
+	fn estimate(&self, node: &T, end_node: &T) -> Option<f32> {
+		Some(self(node, end_node))
+	}
+}
+
+/// A [Heuristic] which always estimates that no distance remains, turning
+/// [astar_path_with_heuristic] into Dijkstra's algorithm as used by
+/// [dijkstra_path](crate::dijkstra_path). Being a unit struct the estimate is known at
+/// compile time and costs nothing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoHeuristic;
+
+impl<T> Heuristic<T> for NoHeuristic {
+	fn estimate(&self, _node: &T, _end_node: &T) -> Option<f32> {
+		Some(0.0)
+	}
+}
+
+/// A [Heuristic] which estimates that no distance remains from any node, spelling out as an
+/// estimate of zero what [NoHeuristic] expresses as the absence of a heuristic. The two
+/// search identically and, being unit structs, cost nothing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ZeroHeuristic;
+
+impl<T> Heuristic<T> for ZeroHeuristic {
+	fn estimate(&self, _node: &T, _end_node: &T) -> Option<f32> {
+		Some(0.0)
+	}
+}
+
+/// The closure form of [NoHeuristic], estimating that no distance remains from any node,
+/// for APIs expecting a plain `Fn(&T, &T) -> f32`
+pub fn zero_heuristic<T>(_node: &T, _end_node: &T) -> f32 {
+	0.0
+}
+
+/// Wraps a `Fn(&T, &T) -> Option<f32>` closure as a [Heuristic] able to prune nodes, such
+/// as those on the wrong side of a wall, by returning `None` for any node which can never
+/// lead to the goal
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_with_heuristic, Pruning};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 0.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 0.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 0.0));
+/// nodes.insert(3, (vec![], 0.0));
+/// // node 1 is known to be a dead end
+/// let heuristic = Pruning(|n: &i32, _: &i32| if *n == 1 { None } else { Some(0.0) });
+/// assert_eq!(Some(vec![0, 2, 3]), astar_path_with_heuristic(0, &nodes, 3, heuristic));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Pruning<F>(pub F);
+
+impl<T, F> Heuristic<T> for Pruning<F>
+where
+	F: Fn(&T, &T) -> Option<f32>,
+{
+	fn estimate(&self, node: &T, end_node: &T) -> Option<f32> {
+		(self.0)(node, end_node)
+	}
+}
+
+/// Finds the path from `start_node` to `end_node` scoring each node by the distance
+/// traveled to it plus the estimate of `heuristic`, ignoring the weights held by `nodes`.
+///
+/// Passing [NoHeuristic] makes this Dijkstra's algorithm, finding the path with the
+/// shortest total distance, whereas an admissible heuristic, one never overestimating the
+/// distance remaining, finds a path just as short while expanding fewer nodes. Any node
+/// the heuristic estimates as `None` is pruned, it is never queued nor expanded, and if
+/// `start_node` is pruned no path is found. Each node is estimated at most once. Routes
+/// which tie on score are ordered as per [TieBreak::DistanceTraveled].
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_with_heuristic, dijkstra_path, NoHeuristic};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 4.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 100.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// let path = astar_path_with_heuristic(0, &nodes, 2, NoHeuristic);
+/// assert_eq!(dijkstra_path(0, &nodes, 2), path);
+/// let path = astar_path_with_heuristic(0, &nodes, 2, |n: &i32, end: &i32| (end - n) as f32);
+/// assert_eq!(Some(vec![0, 1, 2]), path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_heuristic<T, H>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	heuristic: H,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	H: Heuristic<T>,
+{
+	heuristic_search(
+		start_node,
+		nodes,
+		end_node,
+		heuristic,
+		TieBreak::DistanceTraveled,
+		&mut SearchStats::default(),
+		&mut NoListener,
+	)
+}
+
+/// Behaves like [astar_path_with_heuristic] but orders routes which tie on score as set by
+/// `tie_break`, and also reports statistics of the search as per
+/// [astar_path_with_stats](crate::astar_path_with_stats), useful for measuring how many
+/// expansions each ordering takes.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_with_heuristic_stats, NoHeuristic, TieBreak};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 0.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 0.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 0.0));
+/// nodes.insert(3, (vec![], 0.0));
+/// for tie_break in [TieBreak::DistanceTraveled, TieBreak::StoredWeight].iter() {
+///     let (path, stats) = astar_path_with_heuristic_stats(0, &nodes, 3, NoHeuristic, *tie_break);
+///     assert_eq!(3, path.unwrap().len());
+///     assert!(stats.expansions >= 2);
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_heuristic_stats<T, H>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	heuristic: H,
+	tie_break: TieBreak,
+) -> (Option<Vec<T>>, SearchStats)
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	H: Heuristic<T>,
+{
+	let mut stats = SearchStats::default();
+	let path = heuristic_search(
+		start_node,
+		nodes,
+		end_node,
+		heuristic,
+		tie_break,
+		&mut stats,
+		&mut NoListener,
+	);
+	(path, stats)
+}
+
+/// Runs the search of [astar_path_with_heuristic], ordering tied routes as set by
+/// `tie_break`, recording the work carried out in `stats` and notifying `listener` of its
+/// progress
+#[allow(clippy::type_complexity)]
+fn heuristic_search<T, H, L>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	heuristic: H,
+	tie_break: TieBreak,
+	stats: &mut SearchStats,
+	listener: &mut L,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	H: Heuristic<T>,
+	L: SearchListener<T>,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	// estimates are looked up both to prune neighbours and to score those kept
+	let estimates: RefCell<HashMap<T, Option<f32>>> = RefCell::new(HashMap::new());
+	let estimate = |node: &T| {
+		*estimates
+			.borrow_mut()
+			.entry(*node)
+			.or_insert_with(|| heuristic.estimate(node, &end_node))
+	};
+	estimate(&start_node)?;
+	let lookup_neighbours = stored_neighbours(nodes);
+	let path = search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| Ok(lookup_neighbours(node)?.filter(|(neighbour, _)| estimate(neighbour).is_some())),
+		stored_weight(nodes, |node, _| estimate(node).unwrap_or(f32::INFINITY)),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		stats,
+		listener,
+		|_| (),
+		usize::MAX,
+		f32::INFINITY,
+		a_star_score,
+		tie_break,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option();
+	listener.on_finish(&path);
+	path
+}
+
+/// A heuristic estimating the distance from a node to the goal, paired with the factor it
+/// is scaled by, as taken by [astar_path_with_heuristics]
+pub type WeightedHeuristic<'a, T> = (f32, Box<dyn Fn(&T, &T) -> f32 + 'a>);
+
+/// How [astar_path_with_heuristics] merges several scaled heuristics into one estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combine {
+	/// The largest of the scaled estimates. If every heuristic is admissible, never
+	/// overestimating the distance remaining, and no factor exceeds `1.0` then so is the
+	/// combination, while being at least as informed as any one of them
+	Max,
+	/// The sum of the scaled estimates. Admissible when every heuristic is admissible and
+	/// the factors sum to at most `1.0`, as the result can't exceed the largest estimate.
+	/// Factors summing to more than `1.0` may overestimate, trading optimality of the path
+	/// for fewer expansions
+	WeightedSum,
+}
+
+impl Combine {
+	/// Merges the estimates of `heuristics` from `node` to `end_node`, `0.0` when there are
+	/// none
+	fn estimate<T>(&self, heuristics: &[WeightedHeuristic<'_, T>], node: &T, end_node: &T) -> f32 {
+		let scaled = heuristics
+			.iter()
+			.map(|(factor, heuristic)| factor * heuristic(node, end_node));
+		match self {
+			Combine::Max => scaled.fold(0.0, f32::max),
+			Combine::WeightedSum => scaled.sum(),
+		}
+	}
+}
+
+/// Behaves like [astar_path_with_heuristic] with the estimate of the distance remaining to
+/// `end_node` blended from `heuristics` as set by `combine`, scoring each node by the
+/// distance traveled to it plus the blend and ignoring the weights held by `nodes`. Each
+/// heuristic is scaled by the factor it is paired with, allowing blends such as `0.7` of a
+/// landmark bound with `0.3` of the euclidean distance.
+///
+/// The path returned has the shortest total distance whenever the blend is admissible, see
+/// [Combine] for when each kind of blend is.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_with_heuristics, Combine, WeightedHeuristic};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 0.0));
+/// nodes.insert(1, (vec![(3, 2.0)], 0.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 0.0));
+/// nodes.insert(3, (vec![], 0.0));
+/// let heuristics: Vec<WeightedHeuristic<i32>> = vec![
+///     (0.5, Box::new(|n: &i32, end: &i32| if n == end { 0.0 } else { 1.0 })),
+///     (0.5, Box::new(|n: &i32, end: &i32| (end - n) as f32 / 3.0)),
+/// ];
+/// let path = astar_path_with_heuristics(0, &nodes, 3, &heuristics, Combine::WeightedSum);
+/// assert_eq!(Some(vec![0, 2, 3]), path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_heuristics<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	heuristics: &[WeightedHeuristic<'_, T>],
+	combine: Combine,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_path_with_heuristic(start_node, nodes, end_node, |node: &T, end_node: &T| {
+		combine.estimate(heuristics, node, end_node)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		astar_optimal_cost, astar_path, fixtures::large_grid_nodes, graph::edge_distance,
+		precompute_landmarks,
+	};
+	use std::collections::HashSet;
+
+	/// Records every node expanded or queued by a search
+	#[derive(Default)]
+	struct Explored(HashSet<i32>);
+
+	impl SearchListener<i32> for Explored {
+		fn on_expand(&mut self, node: &i32, _score: f32, _distance: f32) {
+			self.0.insert(*node);
+		}
+		fn on_relax(&mut self, _from: &i32, to: &i32, _new_score: f32) {
+			self.0.insert(*to);
+		}
+	}
+
+	#[test]
+	/// Pruning the right half of the grid, which a wall seals off from the goal, confines
+	/// the search to the left half without changing the path found
+	fn pruned_region_is_never_explored() {
+		let size = 10;
+		let nodes = large_grid_nodes(size);
+		let manhattan = move |a: &i32, b: &i32| {
+			((a % size - b % size).abs() + (a / size - b / size).abs()) as f32
+		};
+		let (start, end) = (40, 92);
+		let heuristic = Pruning(|node: &i32, end: &i32| {
+			if node % size >= 5 {
+				None
+			} else {
+				Some(manhattan(node, end))
+			}
+		});
+		let mut explored = Explored::default();
+		let path = heuristic_search(
+			start,
+			&nodes,
+			end,
+			heuristic,
+			TieBreak::DistanceTraveled,
+			&mut SearchStats::default(),
+			&mut explored,
+		);
+		assert_eq!(
+			astar_path_with_heuristic(start, &nodes, end, manhattan),
+			path
+		);
+		assert!(explored.0.contains(&end));
+		assert!(explored.0.iter().all(|node| node % size < 5));
+		// the start itself being pruned leaves nothing to search
+		let pruned = astar_path_with_heuristic(45, &nodes, end, heuristic);
+		assert_eq!(None, pruned);
+	}
+	/// Total distance along `path` through `nodes`
+	#[allow(clippy::type_complexity)]
+	fn path_cost(nodes: &HashMap<i32, (Vec<(i32, f32)>, f32)>, path: &[i32]) -> f32 {
+		path.windows(2)
+			.map(|pair| edge_distance(nodes, &pair[0], &pair[1]).unwrap())
+			.sum()
+	}
+
+	#[test]
+	/// Without a heuristic the search travels the shortest total distance found by the
+	/// separate search of [astar_optimal_cost], ignoring the weights which steer
+	/// [astar_path] elsewhere, while [ZeroHeuristic] and the closure form search identically
+	fn no_heuristic_matches_dijkstra() {
+		let nodes = large_grid_nodes(10);
+		for (start, end) in [(0, 99), (7, 62), (45, 3)].iter() {
+			let path = astar_path_with_heuristic(*start, &nodes, *end, NoHeuristic);
+			let optimal = astar_optimal_cost(*start, &nodes, *end).unwrap();
+			assert!((path_cost(&nodes, path.as_ref().unwrap()) - optimal).abs() < 1.0e-4);
+			let zero = astar_path_with_heuristic(*start, &nodes, *end, ZeroHeuristic);
+			assert_eq!(path, zero);
+			let closure = astar_path_with_heuristic(*start, &nodes, *end, zero_heuristic);
+			assert_eq!(path, closure);
+		}
+		assert_ne!(
+			astar_path(0, &nodes, 99),
+			astar_path_with_heuristic(0, &nodes, 99, NoHeuristic)
+		);
+	}
+
+	#[test]
+	/// Across an open grid of zero weights guided by the Manhattan distance to the corner
+	/// goal every node lies on a route of the optimal score. Preferring the tied route
+	/// which has traveled furthest expands only the nodes along the path, while leaving ties
+	/// to the stored weights, all equal, takes them in the order they were queued and
+	/// expands most of the grid
+	fn ties_prefer_distance_traveled() {
+		let size = 30;
+		let mut nodes = large_grid_nodes(size);
+		for (_, weight) in nodes.values_mut() {
+			*weight = 0.0;
+		}
+		let end = size * size - 1;
+		let manhattan = move |a: &i32, b: &i32| {
+			((a % size - b % size).abs() + (a / size - b / size).abs()) as f32
+		};
+		let (path, stats) =
+			astar_path_with_heuristic_stats(0, &nodes, end, manhattan, TieBreak::DistanceTraveled);
+		let path = path.unwrap();
+		assert_eq!(2 * (size - 1) as usize + 1, path.len());
+		assert_eq!(path.len() - 1, stats.expansions);
+		let (by_weight, by_weight_stats) =
+			astar_path_with_heuristic_stats(0, &nodes, end, manhattan, TieBreak::StoredWeight);
+		assert_eq!(path.len(), by_weight.unwrap().len());
+		assert!(by_weight_stats.expansions > 10 * stats.expansions);
+	}
+	#[test]
+	/// Blending a landmark bound with the manhattan distance, both admissible as no edge is
+	/// shorter than `1.0`, finds a path of the optimal cost either way they're combined
+	fn blended_admissible_heuristics_are_optimal() {
+		let size = 8;
+		// unweighted grid whose edge distances vary by the node they lead into
+		let nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = large_grid_nodes(size)
+			.into_iter()
+			.map(|(node, (neighbours, _))| {
+				let neighbours = neighbours
+					.into_iter()
+					.map(|(n, _)| (n, 1.0 + ((n * 7) % 11) as f32 * 0.37))
+					.collect();
+				(node, (neighbours, 0.0))
+			})
+			.collect();
+		let landmarks = precompute_landmarks(&nodes, &[0, size * size - 1]);
+		let manhattan = move |a: &i32, b: &i32| {
+			((a % size - b % size).abs() + (a / size - b / size).abs()) as f32
+		};
+		let heuristics: Vec<WeightedHeuristic<i32>> = vec![
+			(
+				0.7,
+				Box::new(|a: &i32, b: &i32| landmarks.lower_bound(a, b)),
+			),
+			(0.3, Box::new(manhattan)),
+		];
+		let (start, end) = (3, 58);
+		let optimal = astar_optimal_cost(start, &nodes, end).unwrap();
+		for combine in [Combine::Max, Combine::WeightedSum].iter() {
+			let path = astar_path_with_heuristics(start, &nodes, end, &heuristics, *combine);
+			assert!((path_cost(&nodes, &path.unwrap()) - optimal).abs() < 1.0e-4);
+		}
+	}
+	#[test]
+	/// A heavy weight on the node along the shortest route doesn't steer the blend away
+	/// from it, as like [astar_path_with_heuristic] the weights held by `nodes` are ignored
+	fn blend_ignores_node_weights() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 5.0));
+		nodes.insert(1, (vec![(3, 1.0)], 100.0));
+		nodes.insert(2, (vec![(3, 3.0)], 0.0));
+		nodes.insert(3, (vec![], 2.0));
+		let heuristics: Vec<WeightedHeuristic<i32>> = vec![
+			(
+				1.0,
+				Box::new(|n: &i32, end: &i32| if n == end { 0.0 } else { 1.0 }),
+			),
+			(
+				0.5,
+				Box::new(|n: &i32, end: &i32| (end - n).abs() as f32 / 3.0),
+			),
+		];
+		let optimal = astar_optimal_cost(0, &nodes, 3).unwrap();
+		for combine in [Combine::Max, Combine::WeightedSum].iter() {
+			let path = astar_path_with_heuristics(0, &nodes, 3, &heuristics, *combine);
+			assert_eq!(Some(vec![0, 1, 3]), path);
+			assert_eq!(optimal, path_cost(&nodes, &path.unwrap()));
+		}
+		assert_eq!(Some(vec![0, 2, 3]), astar_path(0, &nodes, 3));
+	}
+}
@@ -0,0 +1,254 @@
+//! Searches over graphs whose nodes are labelled by a dense range of indices `0..n`, stored
+//! in a slice indexed by node rather than a `HashMap`
+//!
+
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{a_star_score, error::AstarError, no_worse_than, DEFAULT_SCORE_EPSILON};
+
+/// Marks a node with no live route in the queue or no recorded predecessor
+const NONE: usize = usize::MAX;
+
+/// Finds the same path as [astar_path](crate::astar_path) through a graph whose nodes are
+/// labelled `0..adjacency.len()`, where `adjacency[node]` holds the
+/// `(neighbour, distance)` pairs and weight of `node`.
+///
+/// Scores, visited nodes and the path back to the start are held in arrays indexed by node
+/// rather than in maps, which is considerably faster for dense integer labels. As with
+/// [astar_path](crate::astar_path) the predecessor of each node is recorded as it is
+/// expanded and the path is walked back from the end node once it is reached.
+///
+/// ```rust
+/// use pathfinding_astar::astar_path_indexed;
+///
+/// let adjacency = vec![
+///     (vec![(1, 5.0), (2, 2.0)], 3.0),
+///     (vec![(3, 4.0)], 2.0),
+///     (vec![(3, 4.0)], 6.0),
+///     (vec![], 0.0),
+/// ];
+/// assert_eq!(Some(vec![0, 2, 3]), astar_path_indexed(0, &adjacency, 3));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path), where a node is missing if
+/// its index is not less than `adjacency.len()`
+#[allow(clippy::type_complexity)]
+#[must_use = "the computed path is discarded if unused"]
+pub fn astar_path_indexed(
+	start_node: usize,
+	adjacency: &[(Vec<(usize, f32)>, f32)],
+	end_node: usize,
+) -> Option<Vec<usize>> {
+	try_astar_path_indexed(start_node, adjacency, end_node).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Runs the search of [astar_path_indexed], returning an [AstarError] in place of a panic
+#[allow(clippy::type_complexity)]
+fn try_astar_path_indexed(
+	start_node: usize,
+	adjacency: &[(Vec<(usize, f32)>, f32)],
+	end_node: usize,
+) -> Result<Option<Vec<usize>>, AstarError<usize>> {
+	let len = adjacency.len();
+	if start_node >= len {
+		return Err(AstarError::MissingStartNode(start_node));
+	}
+	if end_node >= len {
+		return Err(AstarError::MissingEndNode(end_node));
+	}
+	if adjacency[start_node].1.is_nan() {
+		return Err(AstarError::NanScore(start_node));
+	}
+	// best A-Star score recorded for each node, `NaN` until a route to it is found
+	let mut scores: Vec<f32> = vec![f32::NAN; len];
+	// whether each node has been expanded
+	let mut expanded: Vec<bool> = vec![false; len];
+	// the node each node was expanded from
+	let mut predecessors: Vec<usize> = vec![NONE; len];
+	// the `id` of the live route to each node in the queue
+	let mut live: Vec<usize> = vec![NONE; len];
+	let mut queue: BinaryHeap<IndexedEntry> = BinaryHeap::new();
+	let mut next_id = 0;
+
+	scores[start_node] = 0.0;
+	live[start_node] = next_id;
+	queue.push(IndexedEntry {
+		node: start_node,
+		from: NONE,
+		score: 0.0,
+		distance: 0.0,
+		weight: adjacency[start_node].1,
+		id: next_id,
+	});
+	next_id += 1;
+
+	while let Some(current) = queue.pop() {
+		// discard routes superseded since they were queued
+		if live[current.node] != current.id {
+			continue;
+		}
+		live[current.node] = NONE;
+		expanded[current.node] = true;
+		predecessors[current.node] = current.from;
+		if current.node == end_node {
+			let mut path = vec![end_node];
+			let mut node = end_node;
+			while predecessors[node] != NONE {
+				node = predecessors[node];
+				path.push(node);
+			}
+			path.reverse();
+			return Ok(Some(path));
+		}
+		for &(neighbour, distance) in adjacency[current.node].0.iter() {
+			if neighbour >= len {
+				return Err(AstarError::MissingNode(neighbour));
+			}
+			if distance < 0.0 {
+				return Err(AstarError::NegativeDistance(neighbour));
+			}
+			let distance_traveled = current.distance + distance;
+			let weight = adjacency[neighbour].1;
+			let astar_score = a_star_score(distance_traveled, weight);
+			if astar_score.is_nan() || weight.is_nan() {
+				return Err(AstarError::NanRoute {
+					from: current.node,
+					to: neighbour,
+					from_score: current.score,
+					score: astar_score,
+				});
+			}
+			let existing_score = scores[neighbour];
+			let improved = if existing_score.is_nan() {
+				true
+			} else if expanded[neighbour] {
+				!no_worse_than(existing_score, astar_score, DEFAULT_SCORE_EPSILON)
+			} else {
+				no_worse_than(astar_score, existing_score, DEFAULT_SCORE_EPSILON)
+			};
+			if improved {
+				scores[neighbour] = astar_score;
+				live[neighbour] = next_id;
+				queue.push(IndexedEntry {
+					node: neighbour,
+					from: current.node,
+					score: astar_score,
+					distance: distance_traveled,
+					weight,
+					id: next_id,
+				});
+				next_id += 1;
+			}
+		}
+	}
+	Ok(None)
+}
+
+/// A route to a node awaiting processing in the queue of [astar_path_indexed]
+struct IndexedEntry {
+	/// The node this route reaches
+	node: usize,
+	/// The node this route reached `node` from, [NONE] for the start node
+	from: usize,
+	/// A-Star score of the route
+	score: f32,
+	/// Total distance traveled along the route
+	distance: f32,
+	/// The weight of `node`
+	weight: f32,
+	/// Identifies the order in which routes were queued
+	id: usize,
+}
+
+impl IndexedEntry {
+	/// Orders queue entries the same way as the queue of [astar_path](crate::astar_path):
+	/// lowest score, then longest distance traveled, then lowest weight, then the order in
+	/// which they were queued
+	fn tie_break(a: &Self, b: &Self) -> Ordering {
+		let compare = |x: f32, y: f32| x.partial_cmp(&y).unwrap_or(Ordering::Equal);
+		compare(a.score, b.score)
+			.then_with(|| compare(b.distance, a.distance))
+			.then_with(|| compare(a.weight, b.weight))
+			.then_with(|| a.id.cmp(&b.id))
+	}
+}
+
+impl PartialEq for IndexedEntry {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for IndexedEntry {}
+
+impl PartialOrd for IndexedEntry {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for IndexedEntry {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// reversed so the max-heap yields the best route
+		IndexedEntry::tie_break(other, self)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		astar_path,
+		fixtures::{grid_nodes, large_grid_nodes, no_path_nodes},
+	};
+	use std::collections::HashMap;
+
+	/// Copies a graph labelled `0..n` into slice storage indexed by node
+	#[allow(clippy::type_complexity)]
+	fn to_adjacency(nodes: &HashMap<i32, (Vec<(i32, f32)>, f32)>) -> Vec<(Vec<(usize, f32)>, f32)> {
+		(0..nodes.len() as i32)
+			.map(|node| {
+				let (neighbours, weight) = &nodes[&node];
+				let neighbours = neighbours
+					.iter()
+					.map(|(n, distance)| (*n as usize, *distance))
+					.collect();
+				(neighbours, *weight)
+			})
+			.collect()
+	}
+
+	#[test]
+	/// Every path through the grids matches [astar_path]
+	fn indexed_matches_astar_path() {
+		for nodes in [grid_nodes(), large_grid_nodes(12)] {
+			let adjacency = to_adjacency(&nodes);
+			for end in 0..nodes.len() {
+				let expected = astar_path(0, &nodes, end as i32)
+					.map(|path| path.into_iter().map(|n| n as usize).collect::<Vec<usize>>());
+				assert_eq!(expected, astar_path_indexed(0, &adjacency, end));
+			}
+		}
+	}
+	#[test]
+	/// Unreachable end nodes have no path
+	fn indexed_no_path() {
+		let adjacency = to_adjacency(&no_path_nodes());
+		assert_eq!(None, astar_path_indexed(0, &adjacency, 5));
+	}
+	#[test]
+	/// A neighbour outside of the slice is reported as missing
+	fn indexed_missing_node() {
+		let adjacency = vec![(vec![(4, 1.0)], 1.0), (vec![], 1.0)];
+		assert_eq!(
+			Err(AstarError::MissingNode(4)),
+			try_astar_path_indexed(0, &adjacency, 1)
+		);
+		assert_eq!(
+			Err(AstarError::MissingEndNode(2)),
+			try_astar_path_indexed(0, &adjacency, 2)
+		);
+	}
+}
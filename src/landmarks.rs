@@ -0,0 +1,183 @@
+//! Landmark based lower bounds on distance (ALT), used to focus a search towards its goal
+//! on large static graphs
+//!
+
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::{
+	astar_search_with_stats,
+	costs::{shortest_distances, CostMap},
+	SearchStats,
+};
+
+/// Shortest distances from a set of landmark nodes to every node they can reach, produced
+/// by [precompute_landmarks]
+#[derive(Debug, Clone)]
+pub struct LandmarkData<T> {
+	/// For each landmark, the shortest distance from it to every node it can reach
+	distances: Vec<CostMap<T>>,
+}
+
+impl<T> LandmarkData<T>
+where
+	T: Eq + Hash,
+{
+	/// A lower bound on the distance from `from` to `to` derived via the triangle
+	/// inequality, for every landmark `L` the distance `d(L, to) - d(L, from)` can't
+	/// exceed the true distance. Landmarks which can't reach both nodes are ignored and
+	/// `0.0` is returned when nothing can be inferred
+	pub fn lower_bound(&self, from: &T, to: &T) -> f32 {
+		self.distances
+			.iter()
+			.filter_map(|distances| match (distances.get(from), distances.get(to)) {
+				(Some(from), Some(to)) => Some(to - from),
+				_ => None,
+			})
+			.fold(0.0, f32::max)
+	}
+}
+
+/// Runs Dijkstra from each of the `landmarks` to record its shortest distance to every
+/// node, ignoring node weights, for use with [astar_path_alt].
+///
+/// Landmarks spread around the edge of the graph typically give the tightest bounds.
+/// Landmarks which aren't keys of `nodes` reach nothing and contribute no bounds.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::precompute_landmarks;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 0.0));
+/// nodes.insert(1, (vec![(2, 4.0)], 0.0));
+/// nodes.insert(2, (vec![], 0.0));
+/// let landmarks = precompute_landmarks(&nodes, &[0]);
+/// assert_eq!(4.0, landmarks.lower_bound(&1, &2));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn precompute_landmarks<T>(
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	landmarks: &[T],
+) -> LandmarkData<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let distances = landmarks
+		.iter()
+		.map(|landmark| {
+			if nodes.contains_key(landmark) {
+				shortest_distances(*landmark, nodes)
+			} else {
+				HashMap::new()
+			}
+		})
+		.collect();
+	LandmarkData { distances }
+}
+
+/// Behaves like [astar_path](crate::astar_path) but adds the lower bound on the remaining
+/// distance to `end_node` given by `landmark_data` to the weight of each node. The bound
+/// never overestimates, so the search is steered towards the goal and expands fewer nodes.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_alt, precompute_landmarks};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0), (2, 1.0)], 0.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 0.0));
+/// nodes.insert(2, (vec![(3, 9.0)], 0.0));
+/// nodes.insert(3, (vec![], 0.0));
+/// let landmarks = precompute_landmarks(&nodes, &[0]);
+/// assert_eq!(Some(vec![0, 1, 3]), astar_path_alt(0, &nodes, 3, &landmarks));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_alt<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	landmark_data: &LandmarkData<T>,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_path_alt_with_stats(start_node, nodes, end_node, landmark_data).0
+}
+
+/// Behaves like [astar_path_alt] but also reports statistics of the search as per
+/// [astar_path_with_stats](crate::astar_path_with_stats)
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_alt_with_stats<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	landmark_data: &LandmarkData<T>,
+) -> (Option<Vec<T>>, SearchStats)
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let (result, stats) = astar_search_with_stats(start_node, nodes, end_node, |node, weight| {
+		weight + landmark_data.lower_bound(node, &end_node)
+	});
+	(result.into_option(), stats)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{astar_path, astar_path_with_stats};
+
+	/// Builds a `size` by `size` grid without weights where the distance of each edge varies
+	/// by the square it leads into, so the optimal path depends on distance alone
+	#[allow(clippy::type_complexity)]
+	fn uneven_grid(size: i32) -> HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> {
+		let distance = |x: i32, y: i32| 1.0 + ((x * 7 + y * 13) % 11) as f32 * 0.37;
+		let mut nodes = HashMap::new();
+		for x in 0..size {
+			for y in 0..size {
+				let mut neighbours = Vec::new();
+				for (nx, ny) in [(x + 1, y), (x, y + 1), (x - 1, y), (x, y - 1)] {
+					if (0..size).contains(&nx) && (0..size).contains(&ny) {
+						neighbours.push(((nx, ny), distance(nx, ny)));
+					}
+				}
+				nodes.insert((x, y), (neighbours, 0.0));
+			}
+		}
+		nodes
+	}
+
+	#[test]
+	/// Landmarks in the corners of the grid find the same path as the plain search while
+	/// expanding fewer nodes
+	fn alt_matches_plain_with_fewer_expansions() {
+		let nodes = uneven_grid(20);
+		let landmarks = precompute_landmarks(&nodes, &[(0, 0), (19, 0), (0, 19), (19, 19)]);
+		let (plain, plain_stats) = astar_path_with_stats((2, 3), &nodes, (17, 15));
+		let (alt, alt_stats) = astar_path_alt_with_stats((2, 3), &nodes, (17, 15), &landmarks);
+		assert!(plain.is_some());
+		assert_eq!(plain, alt);
+		assert_eq!(plain, astar_path((2, 3), &nodes, (17, 15)));
+		assert!(alt_stats.expansions < plain_stats.expansions);
+	}
+	#[test]
+	/// The bound from a landmark is never more than the true distance
+	fn lower_bound_is_admissible() {
+		let nodes = uneven_grid(6);
+		let landmarks = precompute_landmarks(&nodes, &[(0, 0), (5, 5)]);
+		let truth = shortest_distances((1, 4), &nodes);
+		for (node, distance) in truth.iter() {
+			assert!(landmarks.lower_bound(&(1, 4), node) <= distance + 1.0e-4);
+		}
+	}
+}
@@ -26,8 +26,86 @@
 //! * A vector of neighbours with the same type label and the distance between that neighbour and the current key as an `f32`
 //! * An `f32` weighting for the node which will guide the algorithm
 //!
+//! Every function borrows `nodes` rather than taking ownership, so a graph shared behind
+//! an `Rc` or `Arc` can be passed directly as `&shared` and is dereferenced automatically,
+//! allowing many threads to query the same graph without cloning it.
+//!
+
+use std::{
+	cmp::Ordering,
+	collections::{BinaryHeap, HashMap, HashSet},
+	fmt::Debug,
+	hash::Hash,
+	time::{Duration, Instant},
+};
 
-use std::{collections::HashMap, fmt::Debug, hash::Hash};
+use costs::step_penalty;
+
+mod anytime;
+mod builder;
+mod cache;
+mod contraction;
+mod costs;
+mod dijkstra;
+mod display;
+mod equivalence;
+mod error;
+#[cfg(test)]
+mod fixtures;
+mod graph;
+mod heuristics;
+mod indexed;
+mod landmarks;
+mod options;
+#[cfg(feature = "indexmap")]
+mod ordered;
+mod replay;
+mod streaming;
+mod validate;
+mod warm;
+pub use anytime::AnytimeSearch;
+#[cfg(feature = "async")]
+pub use builder::Edge;
+pub use builder::{Graph, GraphBuilder};
+pub use cache::{costs_to_goals, Pursuit, SourceCache};
+pub use contraction::{contract_chains, ChainMap};
+pub use costs::{
+	astar_optimal_cost, both_trees, cheapest_of, component_diameter, farthest_node,
+	is_path_still_optimal, k_nearest, mutually_reachable, optimal_corridor,
+	optimal_goal_predecessors, path_smoothness, reachable_within_cost, CostMap,
+};
+pub use dijkstra::{
+	cheapest_cycle_through, dijkstra_bidirectional, dijkstra_bidirectional_with_reverse,
+	dijkstra_path,
+};
+pub use display::{format_path, format_path_with_costs, segment_path_at, to_dot};
+pub use equivalence::astar_path_with_eq;
+#[cfg(feature = "async")]
+pub use error::StreamError;
+pub use error::{AstarError, BuildError, NoPath};
+pub use graph::{
+	build_reverse, chebyshev, coarsen, edges, flat_grid_graph, from_edge_list, generate_grid,
+	grid_path_directions, invert_weights, map_labels, merge_graphs, nearest_node, octile,
+	try_from_edge_list, Connectivity,
+};
+pub use heuristics::{
+	astar_path_with_heuristic, astar_path_with_heuristic_stats, astar_path_with_heuristics,
+	zero_heuristic, Combine, Heuristic, NoHeuristic, Pruning, WeightedHeuristic, ZeroHeuristic,
+};
+pub use indexed::astar_path_indexed;
+pub use landmarks::{
+	astar_path_alt, astar_path_alt_with_stats, precompute_landmarks, LandmarkData,
+};
+pub use options::{astar_path_with_options, AstarOptions};
+#[cfg(feature = "indexmap")]
+pub use ordered::astar_path_indexmap;
+pub use replay::{SearchEvent, SearchLog};
+pub use streaming::{astar_path_streaming, NeighbourCache};
+pub use validate::{
+	check_heuristic_consistency, graph_stats, isolated_nodes, validate_graph, GraphIssue,
+	GraphStats,
+};
+pub use warm::{astar_path_warm, WarmStart};
 
 /// Will find the most optimal path from `start_node` to `end_node` if it exists.
 /// The `nodes` data set uses the keys as labels to uniquely identify a node/travel point.
@@ -35,6 +113,14 @@ use std::{collections::HashMap, fmt::Debug, hash::Hash};
 /// * Vector of tuples: `(neighbour_label, distance_to_neighbour)` - used to explore possible paths to traverse
 /// * Weight - the heuristic which helps judge whether a given route is good or bad
 ///
+/// The weight of `start_node` is not counted towards the score of the route, it begins at
+/// `0.0`, so a path can be planned off of a start node with a very large or infinite weight.
+///
+/// Routes awaiting exploration are processed in order of the lowest A-Star score. Where
+/// scores tie the route which has traveled the longest distance goes first, then the route
+/// reaching the node with the lowest stored weight. Routes tied on all three are processed
+/// in the order they were discovered.
+///
 /// For instance:
 ///
 /// ```rust
@@ -50,146 +136,2943 @@ use std::{collections::HashMap, fmt::Debug, hash::Hash};
 /// let path = astar_path(start, &nodes, end).unwrap();
 /// assert_eq!(vec![0, 1, 2], path);
 /// ```
+///
+/// # Panics
+///
+/// If `nodes` doesn't contain `start_node` or `end_node`, a node is referenced as a neighbour
+/// but is not a key of `nodes`, an edge has a negative distance, or a route can't be scored
+/// due to a `NaN` distance or weight.
+/// Use [try_astar_path] to handle these as errors instead
+#[must_use = "the computed path is discarded if unused"]
 #[allow(clippy::type_complexity)]
 pub fn astar_path<T>(
 	start_node: T,
 	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
 	end_node: T,
 ) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	try_astar_path(start_node, nodes, end_node).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Behaves like [astar_path] except that malformed data produces an [AstarError] rather
+/// than a panic, making it safe to call on untrusted data.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{try_astar_path, AstarError};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// assert_eq!(Err(AstarError::MissingEndNode(2)), try_astar_path(0, &nodes, 2));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// assert_eq!(Ok(Some(vec![0, 1, 2])), try_astar_path(0, &nodes, 2));
+/// ```
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn try_astar_path<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Result<Option<Vec<T>>, AstarError<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	try_astar_search(
+		start_node,
+		nodes,
+		end_node,
+		|_, weight| weight,
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.map(PathResult::into_option)
+}
+
+/// Behaves like [astar_path] except that any panic raised by the search is caught and its
+/// message returned as an `Err`, rather than unwinding through the caller.
+///
+/// This is a bridge for code written against the panicking API which must not bring its
+/// thread down on malformed data, prefer [try_astar_path] which reports each failure as a
+/// typed [AstarError] without unwinding. The panic hook still runs, so the message is
+/// printed to stderr by default, and a panic is still an abort when built with
+/// `panic = "abort"`.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_safe;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// assert_eq!(Ok(Some(vec![0, 1, 2])), astar_path_safe(0, &nodes, 2));
+/// assert!(astar_path_safe(7, &nodes, 2).is_err());
+/// ```
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_safe<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Result<Option<Vec<T>>, String>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// `nodes` is only read by the search so a panic part way through can't leave it in an
+	// inconsistent state for the caller to observe, even if `T` isn't `RefUnwindSafe`
+	std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+		astar_path(start_node, nodes, end_node)
+	}))
+	.map_err(|payload| {
+		if let Some(message) = payload.downcast_ref::<String>() {
+			message.clone()
+		} else if let Some(message) = payload.downcast_ref::<&str>() {
+			message.to_string()
+		} else {
+			"the search panicked".to_string()
+		}
+	})
+}
+
+/// Tolerance within which two A-Star scores are treated as equal, `1e-4`.
+///
+/// Scores are built by summing `f32` distances so two routes of logically identical
+/// length can differ by a tiny rounding error. When relaxing a route to an already
+/// discovered node, a new score within this tolerance of the recorded one counts as a tie
+/// and, as with exact ties, the newly discovered route replaces the recorded one. This keeps
+/// the choice between near-identical routes down to the order in which they are discovered
+/// rather than floating point noise. Use [astar_path_with_epsilon] to choose a different
+/// tolerance.
+///
+/// The rounding error of an `f32` grows with its magnitude, so for scores beyond `100.0`
+/// the tolerance widens to [RELATIVE_SCORE_EPSILON] of the recorded score.
+pub const DEFAULT_SCORE_EPSILON: f32 = 1e-4;
+
+/// Fraction of a recorded A-Star score within which a new score is also treated as equal,
+/// `1e-6`, roughly eight units in the last place of an `f32`.
+///
+/// Once scores grow large the rounding error from summing distances in a different order
+/// can exceed any fixed tolerance, leaving one of two logically identical routes fractionally
+/// better. Without a relative tolerance such a route to an already expanded node would
+/// reopen it and everything expanded beyond it. Whichever tolerance is wider of this and
+/// the `epsilon` of the search is used, an `epsilon` of `0.0` disables both.
+pub const RELATIVE_SCORE_EPSILON: f32 = 1e-6;
+
+/// Behaves like [astar_path] with scores within `epsilon` of each other being treated
+/// as equal when deciding whether a newly discovered route replaces a recorded one, see
+/// [DEFAULT_SCORE_EPSILON]. An `epsilon` of `0.0` compares scores exactly.
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_epsilon<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	epsilon: f32,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_search(
+		start_node,
+		nodes,
+		end_node,
+		|_, weight| weight,
+		epsilon,
+		|_| None,
+	)
+	.into_option()
+}
+
+/// Behaves like [astar_path] except that each node's weight is clamped into the
+/// range `[min_weight, max_weight]` before it is used to score a path.
+///
+/// The stored `nodes` are left untouched so this is a cheap way of testing how
+/// sensitive a route is to the magnitude of the weights. Choosing a range wide
+/// enough to contain every weight reproduces the result of [astar_path].
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_clamped;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 20.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let path = astar_path_clamped(0, &nodes, 2, 0.0, 10.0).unwrap();
+/// assert_eq!(vec![0, 1, 2], path);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `min_weight` is greater than `max_weight` or either is `NaN`, along
+/// with the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_clamped<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	min_weight: f32,
+	max_weight: f32,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_search(
+		start_node,
+		nodes,
+		end_node,
+		|_, weight| weight.clamp(min_weight, max_weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+	)
+	.into_option()
+}
+
+/// Behaves like [astar_path] except that the weights of any nodes found in `overrides`
+/// are replaced by the weight given there, without needing to clone or modify `nodes`.
+///
+/// Useful for "what if this tile were harder" experiments and sensitivity analysis.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_override;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 2.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// let mut overrides = HashMap::new();
+/// overrides.insert(1, 10.0);
+/// let path = astar_path_override(0, &nodes, 3, &overrides).unwrap();
+/// assert_eq!(vec![0, 2, 3], path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_override<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	overrides: &CostMap<T>,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_search(
+		start_node,
+		nodes,
+		end_node,
+		|node, weight| *overrides.get(node).unwrap_or(&weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+	)
+	.into_option()
+}
+
+/// Behaves like [astar_path] except that routes are steered away from a set of `dangers`,
+/// such as guard positions for stealth AI.
+///
+/// Each node's weight is increased by `penalty / (1 + d)` where `d` is the graph distance
+/// from the nearest danger to the node, so the closer a node is to danger the harder it
+/// appears. Nodes which can't be reached from any danger are unaffected. The distances are
+/// precomputed with a single search outward from all of the dangers at once.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_avoid_proximity;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 0.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 0.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 0.0));
+/// nodes.insert(3, (vec![], 0.0));
+/// let path = astar_path_avoid_proximity(0, &nodes, 3, &[1], 10.0).unwrap();
+/// assert_eq!(vec![0, 2, 3], path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_avoid_proximity<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	dangers: &[T],
+	penalty: f32,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let danger_distances = costs::shortest_distances_from_any(dangers, nodes);
+	astar_search(
+		start_node,
+		nodes,
+		end_node,
+		|node, weight| match danger_distances.get(node) {
+			Some(distance) => weight + penalty / (1.0 + distance),
+			None => weight,
+		},
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+	)
+	.into_option()
+}
+
+/// A path along with a breakdown of how it was scored, produced by [astar_path_report]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathReport<T> {
+	/// The most optimal path from start to end, inclusive of both
+	pub nodes: Vec<T>,
+	/// Total distance traveled along the path
+	pub total_distance: f32,
+	/// Sum of the weights of every node on the path, including the start and end
+	pub total_weight: f32,
+	/// The A-Star score of the path, i.e. the total distance plus the weight of the end node
+	pub final_score: f32,
+}
+
+/// Behaves like [astar_path] but reports how much of the result was driven by distance
+/// versus weight, allowing the decomposition of the score to be audited.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_report;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let report = astar_path_report(0, &nodes, 2).unwrap();
+/// assert_eq!(vec![0, 1, 2], report.nodes);
+/// assert_eq!(9.0, report.total_distance);
+/// assert_eq!(11.0, report.total_weight);
+/// assert_eq!(15.0, report.final_score);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_report<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<PathReport<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let path = astar_path(start_node, nodes, end_node)?;
+	let mut total_distance = 0.0;
+	for pair in path.windows(2) {
+		// every step of a found path is an edge of the graph
+		total_distance += graph::edge_distance(nodes, &pair[0], &pair[1])?;
+	}
+	let total_weight = path.iter().map(|node| nodes[node].1).sum();
+	let final_score = a_star_score(total_distance, nodes[&end_node].1);
+	Some(PathReport {
+		nodes: path,
+		total_distance,
+		total_weight,
+		final_score,
+	})
+}
+
+/// The outcome of a search made with [astar_handle], giving the cost of the path without
+/// building its nodes until they are asked for
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathHandle<T: Eq + Hash> {
+	/// Node the path begins from
+	start_node: T,
+	/// The node the path leads to
+	goal: T,
+	/// Total distance traveled along the path
+	cost: f32,
+	/// The node each node expanded by the search, along with the goal, was reached from
+	predecessors: HashMap<T, T>,
+}
+
+impl<T> PathHandle<T>
+where
+	T: Eq + Hash + Copy,
+{
+	/// The node the path leads to
+	pub fn goal(&self) -> T {
+		self.goal
+	}
+	/// Total distance traveled along the path
+	pub fn cost(&self) -> f32 {
+		self.cost
+	}
+	/// Number of nodes along the path, including the start and goal, counted by walking
+	/// back from the goal without building the path
+	pub fn len(&self) -> usize {
+		self.route().len()
+	}
+	/// Always `false` as a path holds at least the start node
+	pub fn is_empty(&self) -> bool {
+		false
+	}
+	/// Builds the nodes of the path, from start to goal inclusive, by walking back from the
+	/// goal to the start
+	#[must_use = "the computed path is discarded if unused"]
+	pub fn into_path(self) -> Vec<T> {
+		self.route().path()
+	}
+	/// The route to the goal along the recorded predecessors
+	fn route(&self) -> Route<'_, T> {
+		Route {
+			node: self.goal,
+			from: self.predecessors.get(&self.goal).copied(),
+			start_node: self.start_node,
+			predecessors: &self.predecessors,
+		}
+	}
+}
+
+/// Behaves like [astar_path] but returns a [PathHandle], for callers running many queries
+/// which mostly need only the cost of each path.
+///
+/// The handle keeps the node each node was reached from rather than the path itself, the
+/// path is only walked back from the goal when [PathHandle::into_path] is called so callers
+/// after nothing but the cost never pay for building it.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_handle;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let handle = astar_handle(0, &nodes, 2).unwrap();
+/// assert_eq!(9.0, handle.cost());
+/// assert_eq!(vec![0, 1, 2], handle.into_path());
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_handle<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<PathHandle<T>>
 where
 	T: Eq + Hash + Debug + Clone + Copy,
 {
 	// ensure nodes data contains start and end points
-	if !nodes.contains_key(&start_node) {
-		panic!("Node data does not contain start node {:?}", start_node);
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let mut weight = stored_weight(nodes, |_, weight| weight);
+	let mut state = SearchState::new(
+		start_node,
+		&mut weight,
+		&mut |_: &Route<T>| (),
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e));
+	let halt = advance_by(
+		&mut state,
+		|node, _, _| *node == end_node,
+		stored_neighbours(nodes),
+		weight,
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+		&mut NoListener,
+		|_| (),
+		usize::MAX,
+		f32::INFINITY,
+		a_star_score,
+	)
+	.unwrap_or_else(|e| panic!("{}", e));
+	match halt {
+		Halt::Goal(goal, cost) => Some(PathHandle {
+			start_node,
+			goal,
+			cost,
+			predecessors: state.predecessors,
+		}),
+		Halt::Ended(_) => None,
 	}
-	if !nodes.contains_key(&end_node) {
-		panic!("Node data does not contain end node {:?}", end_node);
+}
+
+/// A path along with the version of the graph it was computed against, produced by
+/// [astar_path_versioned], so a cached path can be recognised as stale once the graph has
+/// changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathWithVersion<T> {
+	/// The path from start to goal, inclusive of both
+	path: Vec<T>,
+	/// Version of the graph the path was computed against
+	version: u64,
+}
+
+impl<T> PathWithVersion<T> {
+	/// The nodes of the path, from start to goal inclusive
+	pub fn path(&self) -> &[T] {
+		&self.path
 	}
-	// retreive the weight of the start point
-	let start_weight: f32 = match nodes.get(&start_node) {
-		Some(x) => x.1,
-		None => panic!("Unable to find starting node weight"),
-	};
+	/// Version of the graph the path was computed against
+	pub fn version(&self) -> u64 {
+		self.version
+	}
+	/// Whether the graph has changed since the path was computed, i.e. `current_version`
+	/// differs from the version the path was computed against
+	pub fn is_stale(&self, current_version: u64) -> bool {
+		self.version != current_version
+	}
+	/// Takes the nodes of the path, from start to goal inclusive
+	#[must_use = "the computed path is discarded if unused"]
+	pub fn into_path(self) -> Vec<T> {
+		self.path
+	}
+}
+
+/// Behaves like [astar_path] but bundles the path with `version`, the version of `nodes`
+/// it was computed against, for caches which need to know when a path has gone stale.
+///
+/// `version` may be a counter the caller bumps whenever they edit the graph, or
+/// [graph_checksum] of `nodes` when edits aren't tracked.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_versioned, graph_checksum};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let cached = astar_path_versioned(0, &nodes, 2, graph_checksum(&nodes)).unwrap();
+/// assert!(!cached.is_stale(graph_checksum(&nodes)));
+/// nodes.get_mut(&1).unwrap().1 = 7.0;
+/// assert!(cached.is_stale(graph_checksum(&nodes)));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_versioned<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	version: u64,
+) -> Option<PathWithVersion<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let path = astar_path(start_node, nodes, end_node)?;
+	Some(PathWithVersion { path, version })
+}
 
-	// Every time we process a new node we add it to a map.
-	// If a node has already been recorded then we replace it if it has a better a-star score (smaller number)
-	// otherwise we discard it.
-	// This is used to optimise the searching whereby if we find a new path to a previously
-	// processed node we can quickly decide to discard or explore the new route
-	let mut node_astar_scores: HashMap<T, f32> = HashMap::new();
+/// Computes a checksum of every node, neighbour, distance and weight of `nodes`, for use as
+/// the version of [astar_path_versioned] when edits to the graph aren't tracked. Any edit
+/// almost certainly changes the checksum, including reordering neighbours which can affect
+/// tie-breaks, while the order in which nodes are stored doesn't matter.
+///
+/// The checksum is computed afresh over the whole graph each time. It is only stable within
+/// a single build of a program so shouldn't be persisted.
+#[allow(clippy::type_complexity)]
+pub fn graph_checksum<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> u64
+where
+	T: Hash,
+{
+	use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+	// combine the hash of each node by addition so the order they are visited is irrelevant
+	nodes
+		.iter()
+		.map(|(node, (neighbours, weight))| {
+			let mut hasher = DefaultHasher::new();
+			node.hash(&mut hasher);
+			weight.to_bits().hash(&mut hasher);
+			for (neighbour, distance) in neighbours.iter() {
+				neighbour.hash(&mut hasher);
+				distance.to_bits().hash(&mut hasher);
+			}
+			hasher.finish()
+		})
+		.fold(0, u64::wrapping_add)
+}
 
-	// add starting node a-star score to data set (starting node score is just its weight)
-	node_astar_scores.insert(start_node, start_weight);
+/// Behaves like [astar_path] but returns both the forward path and the same path
+/// reversed, handy for undoing a movement along it.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_with_reverse;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let (forward, reverse) = astar_path_with_reverse(0, &nodes, 2).unwrap();
+/// assert_eq!(vec![0, 1, 2], forward);
+/// assert_eq!(vec![2, 1, 0], reverse);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_reverse<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<(Vec<T>, Vec<T>)>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let forward = astar_path(start_node, nodes, end_node)?;
+	let reverse = forward.iter().rev().copied().collect();
+	Some((forward, reverse))
+}
 
-	// create a queue of nodes to be processed based on discovery
-	// of form (current_node, a_star_score, vec_previous_nodes_traversed, total_distance_traversed)
-	// start by add starting node to queue
-	let mut queue = vec![(
-		start_node,
-		start_weight, // we haven't moved so starting node score is just its weight
-		Vec::<T>::new(),
-		0.0,
-	)];
-
-	// If a path exists then the end node will shift to the beginning of the queue and we can return it.
-	// If a path does not exist the `queue` will shrink to length 0 and we return `None` through a check
-	//  at the end of each loop iteration.
-	while queue[0].0 != end_node {
-		// Remove the first element ready for processing
-		let current_path = queue.swap_remove(0);
-		// Grab the neighbours with their distances from the current path so we can explore each
-		let neightbours = match nodes.get(&current_path.0) {
-			Some(x) => &x.0,
-			None => panic!(
-				"Node {:?} is not a key in the `nodes` data set",
-				current_path.0
-			),
-		};
-		// Process each new path
-		for n in neightbours.iter() {
-			let distance_traveled_so_far: f32 = current_path.3;
-			let distance_to_this_neighbour: f32 = n.1;
-			// Calculate the total distance from the start to this neighbour node
-			let distance_traveled = distance_traveled_so_far + distance_to_this_neighbour;
-			let node_weight: f32 = match nodes.get(&n.0) {
-				Some(x) => x.1,
-				None => panic!("Unable to find node weight for neighbour {:?}, key probably doesn't exist in `nodes` data set", &n),
-			};
+/// Behaves like [astar_path] but collects the path into any container implementing
+/// `FromIterator`, such as a `VecDeque`, saving a conversion at the call site.
+///
+/// ```rust
+/// use std::collections::{HashMap, VecDeque};
+/// use pathfinding_astar::astar_path_collect;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let path: VecDeque<i32> = astar_path_collect(0, &nodes, 2).unwrap();
+/// assert_eq!(VecDeque::from(vec![0, 1, 2]), path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_collect<T, C>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<C>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	C: FromIterator<T>,
+{
+	astar_path(start_node, nodes, end_node).map(|path| path.into_iter().collect())
+}
+
+/// Finds the same path as [astar_path] along with a parallel vector holding the stored
+/// weight of each node of the path, aligned by index, so the difficulty of the terrain
+/// along the route can be inspected at a glance.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_with_weights;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let (path, weights) = astar_path_with_weights(0, &nodes, 2).unwrap();
+/// assert_eq!(vec![0, 1, 2], path);
+/// assert_eq!(vec![3.0, 2.0, 6.0], weights);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_weights<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<(Vec<T>, Vec<f32>)>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let path = astar_path(start_node, nodes, end_node)?;
+	// every node of a path found has been looked up by the search
+	let weights = path.iter().map(|node| nodes[node].1).collect();
+	Some((path, weights))
+}
+
+/// Finds the same path as [astar_path] but gives each node as its index in `index_of`,
+/// allowing paths which are cached in bulk to be stored as compact `u32` indices rather
+/// than full labels.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_as_indices;
+///
+/// let mut nodes: HashMap<&str, (Vec<(&str, f32)>, f32)> = HashMap::new();
+/// nodes.insert("a", (vec![("b", 5.0)], 3.0));
+/// nodes.insert("b", (vec![("a", 5.0), ("c", 4.0)], 2.0));
+/// nodes.insert("c", (vec![("b", 4.0)], 6.0));
+/// let index_of: HashMap<&str, u32> = [("a", 0), ("b", 1), ("c", 2)].into_iter().collect();
+/// assert_eq!(Some(vec![0, 1, 2]), astar_path_as_indices("a", &nodes, "c", &index_of));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path], or if a node of the path has no entry in
+/// `index_of`
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_as_indices<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	index_of: &HashMap<T, u32>,
+) -> Option<Vec<u32>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let path = astar_path(start_node, nodes, end_node)?;
+	Some(
+		path.iter()
+			.map(|node| match index_of.get(node) {
+				Some(index) => *index,
+				None => panic!("Path node {:?} has no entry in `index_of`", node),
+			})
+			.collect(),
+	)
+}
+
+/// Finds only the first node to move to from `start_node` along the most optimal path
+/// to `end_node`, useful for AI which only needs its immediate next move.
+///
+/// Returns `None` if no path exists and `start_node` itself if it is already the `end_node`.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::next_step;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// assert_eq!(Some(1), next_step(0, &nodes, 2));
+/// assert_eq!(Some(2), next_step(2, &nodes, 2));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn next_step<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let path = astar_path(start_node, nodes, end_node)?;
+	// a path of a single node means we're already at the end
+	Some(*path.get(1).unwrap_or(&path[0]))
+}
+
+/// How many nodes [astar_path_timed] expands between each check of the elapsed time
+pub const DEFAULT_TIMEOUT_CHECK_INTERVAL: usize = 64;
+
+/// Outcome of a search which may be abandoned before it completes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathResult<T> {
+	/// The most optimal path from start to end, inclusive of both
+	Found(Vec<T>),
+	/// The search completed and no route to the end node exists
+	NoPath,
+	/// The search ran out of time before it could complete
+	Timeout,
+	/// The search expanded the maximum number of nodes it was allowed to before it could
+	/// complete
+	ExpansionLimit,
+}
+
+impl<T> PathResult<T> {
+	/// Converts into the `Option` returned by [astar_path], treating a search which was
+	/// abandoned the same as no path being found
+	pub fn into_option(self) -> Option<Vec<T>> {
+		match self {
+			PathResult::Found(path) => Some(path),
+			PathResult::NoPath | PathResult::Timeout | PathResult::ExpansionLimit => None,
+		}
+	}
+}
+
+/// Behaves like [astar_path] but reports the absence of a path as the error [NoPath],
+/// allowing it to be propagated with `?` alongside other errors.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_result, NoPath};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![], 6.0));
+/// assert_eq!(Ok(vec![0, 1, 2]), astar_path_result(0, &nodes, 2));
+/// assert_eq!(Err(NoPath), astar_path_result(2, &nodes, 0));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_result<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Result<Vec<T>, NoPath>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_path(start_node, nodes, end_node).ok_or(NoPath)
+}
+
+/// Behaves like [astar_path] but makes the path independent of the order in which
+/// neighbours are listed in `nodes`.
+///
+/// Where routes tie on A-Star score, distance traveled and stored weight, the route to the
+/// node whose label sorts lowest is processed first, rather than the route discovered
+/// first. With the order in which nodes are processed fixed, tied routes into a node are
+/// always discovered in the same order and the last of them to be discovered is kept.
+/// This gives the same path however the neighbour lists are shuffled, which is useful when
+/// comparing against other A-Star implementations on graphs of equal weights.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_ordered;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(2, 1.0), (1, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// let path = astar_path_ordered(0, &nodes, 3);
+/// nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 1.0));
+/// assert_eq!(path, astar_path_ordered(0, &nodes, 3));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_ordered<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy + Ord,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		stored_neighbours(nodes),
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+		&mut NoListener,
+		|route| route.node(),
+		usize::MAX,
+		f32::INFINITY,
+		a_star_score,
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()
+}
+
+/// Behaves like [astar_path] over a grid labelled by `(x, y)` cell coordinates, such as one
+/// built by [generate_grid], except that where several routes into a cell tie the one
+/// making the fewest turns is kept.
+///
+/// On open terrain many routes share the same cost, such as a staircase of alternating
+/// moves and an L of two straight runs, and which is returned otherwise depends on the
+/// order they're discovered. Preferring fewer turns gives the natural looking straight runs
+/// without changing the cost of the path, as the bias only settles ties. Use
+/// [astar_path_grid_with_turn_bias] to opt out.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_grid, grid_path_directions};
+///
+/// let mut nodes: HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> = HashMap::new();
+/// for x in 0..3 {
+///     for y in 0..3 {
+///         let neighbours = [(x + 1, y), (x, y + 1)]
+///             .iter()
+///             .filter(|(nx, ny)| *nx < 3 && *ny < 3)
+///             .map(|cell| (*cell, 1.0))
+///             .collect();
+///         nodes.insert((x, y), (neighbours, 1.0));
+///     }
+/// }
+/// let path = astar_path_grid((0, 0), &nodes, (2, 2)).unwrap();
+/// let directions = grid_path_directions(&path);
+/// // a single turn rather than a staircase
+/// assert_eq!(1, directions.windows(2).filter(|pair| pair[0] != pair[1]).count());
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_grid(
+	start_node: (i32, i32),
+	nodes: &HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)>,
+	end_node: (i32, i32),
+) -> Option<Vec<(i32, i32)>> {
+	astar_path_grid_with_turn_bias(start_node, nodes, end_node, true)
+}
+
+/// Behaves like [astar_path_grid] with the preference for routes making fewer turns
+/// applied only when `turn_bias` is `true`, otherwise ties are settled as per [astar_path]
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_grid_with_turn_bias(
+	start_node: (i32, i32),
+	nodes: &HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)>,
+	end_node: (i32, i32),
+	turn_bias: bool,
+) -> Option<Vec<(i32, i32)>> {
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	// turns made along each route ranked so far, keyed by the cell it reaches and the cell
+	// it was discovered from, so the turns of a route follow from those of the route it
+	// extends by one step
+	let mut turns: HashMap<((i32, i32), Option<(i32, i32)>), usize> = HashMap::new();
+	let step = |from: (i32, i32), to: (i32, i32)| (to.0 - from.0, to.1 - from.1);
+	search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		stored_neighbours(nodes),
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+		&mut NoListener,
+		|route| {
+			if !turn_bias {
+				return 0;
+			}
+			let (node, from) = (route.node(), route.from());
+			let made = match (from, route.previous()) {
+				(Some(from), Some(previous)) => {
+					let before = turns.get(&(from, Some(previous))).copied().unwrap_or(0);
+					before + usize::from(step(previous, from) != step(from, node))
+				}
+				_ => 0,
+			};
+			turns.insert((node, from), made);
+			made
+		},
+		usize::MAX,
+		f32::INFINITY,
+		a_star_score,
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()
+}
+
+/// Receives notifications as a search made with [astar_path_with_listener] progresses,
+/// allowing reusable instrumentation such as counters, loggers or visualisers to be
+/// written as a type rather than closures capturing state. Every method does nothing by
+/// default so only those of interest need implementing.
+pub trait SearchListener<T> {
+	/// Called as `node` is taken from the head of the queue to have its neighbours explored,
+	/// along with the A-Star score and distance traveled of the route which reached it
+	fn on_expand(&mut self, _node: &T, _score: f32, _distance: f32) {}
+	/// Called when a route from `from` to its neighbour `to` improves upon any route to `to`
+	/// found so far and is queued, scoring `new_score`
+	fn on_relax(&mut self, _from: &T, _to: &T, _new_score: f32) {}
+	/// Called once the search has completed with the path found, if any
+	fn on_finish(&mut self, _path: &Option<Vec<T>>) {}
+}
+
+/// A [SearchListener] which ignores every notification, for searches nobody is listening to
+pub(crate) struct NoListener;
+
+impl<T> SearchListener<T> for NoListener {}
+
+/// The unit type ignores every notification, for callers which must supply a listener but
+/// have no interest in one
+impl<T> SearchListener<T> for () {}
+
+/// Behaves like [astar_path] while notifying `listener` of each node expanded, each route
+/// queued and finally of the path found.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_with_listener, SearchListener};
+///
+/// #[derive(Default)]
+/// struct Trace(Vec<i32>);
+///
+/// impl SearchListener<i32> for Trace {
+///     fn on_expand(&mut self, node: &i32, _score: f32, _distance: f32) {
+///         self.0.push(*node);
+///     }
+/// }
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let mut trace = Trace::default();
+/// let path = astar_path_with_listener(0, &nodes, 2, &mut trace);
+/// assert_eq!(Some(vec![0, 1, 2]), path);
+/// assert_eq!(vec![0, 1], trace.0);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_listener<T, L>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	listener: &mut L,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	L: SearchListener<T>,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let path = search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		stored_neighbours(nodes),
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+		listener,
+		|_| (),
+		usize::MAX,
+		f32::INFINITY,
+		a_star_score,
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option();
+	listener.on_finish(&path);
+	path
+}
+
+/// Measurements of the work carried out by a search, see [astar_path_with_stats]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SearchStats {
+	/// Number of nodes taken from the queue and expanded to discover their neighbours
+	pub expansions: usize,
+	/// The largest number of routes awaiting processing at any one time, there is at
+	/// most one route to each node
+	pub max_frontier: usize,
+}
+
+/// How the queue orders routes awaiting processing which tie on A-Star score, as taken by
+/// [astar_path_with_heuristic_stats]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+	/// The route which has traveled furthest goes first, then the route reaching the node
+	/// with the lowest stored weight, as with [astar_path]. When scores are made up of the
+	/// distance traveled plus an estimate of the distance remaining this is the standard
+	/// A-Star preference for the route furthest along, which on open grids where many
+	/// routes share the same score heads straight for the goal rather than expanding every
+	/// tied route
+	DistanceTraveled,
+	/// The route reaching the node with the lowest stored weight goes first, regardless of
+	/// the distance traveled
+	StoredWeight,
+}
+
+impl Default for TieBreak {
+	fn default() -> Self {
+		TieBreak::DistanceTraveled
+	}
+}
+
+/// Behaves like [astar_path] but also reports statistics of the search, useful for
+/// comparing how much work different approaches take to find a path.
+///
+/// The search stops as soon as `end_node` reaches the head of the queue, so nodes still
+/// awaiting processing at that point don't count towards the expansions.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_with_stats;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let (path, stats) = astar_path_with_stats(0, &nodes, 2);
+/// assert_eq!(Some(vec![0, 1, 2]), path);
+/// assert_eq!(2, stats.expansions);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_stats<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> (Option<Vec<T>>, SearchStats)
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let (result, stats) = astar_search_with_stats(start_node, nodes, end_node, |_, weight| weight);
+	(result.into_option(), stats)
+}
+
+/// Finds up to `count` paths from `start_node` to `end_node` which share no edges, such as
+/// for routing network traffic with redundancy.
+///
+/// The best path is found as per [astar_path], its edges are then removed and the search
+/// repeated on what remains. Paths are returned best first and fewer than `count` are
+/// returned when the graph runs out of routes. Every path is found greedily so a pair of
+/// disjoint routes may exist which this misses once the best path has claimed an edge of
+/// each. Nodes may be shared between paths. As a path from a node to itself uses no
+/// edges, at most one is returned when `start_node` is `end_node`.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_edge_disjoint_paths;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 2.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 2.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// let paths = astar_edge_disjoint_paths(0, &nodes, 3, 3);
+/// assert_eq!(vec![vec![0, 1, 3], vec![0, 2, 3]], paths);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed paths are discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_edge_disjoint_paths<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	count: usize,
+) -> Vec<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let mut remaining = nodes.clone();
+	let mut paths = Vec::new();
+	while paths.len() < count {
+		let path = match astar_path(start_node, &remaining, end_node) {
+			Some(x) => x,
+			None => break,
+		};
+		// remove every edge along the path, including parallel edges between the same nodes
+		for step in path.windows(2) {
+			if let Some((neighbours, _)) = remaining.get_mut(&step[0]) {
+				neighbours.retain(|(neighbour, _)| *neighbour != step[1]);
+			}
+		}
+		let uses_edges = path.len() > 1;
+		paths.push(path);
+		// without any edges removed the same path would be found again
+		if !uses_edges {
+			break;
+		}
+	}
+	paths
+}
+
+/// A [SearchListener] which reports an estimate of how far a search made with
+/// [astar_path_with_listener] has progressed to `progress`, for driving a progress bar
+/// during long searches.
+///
+/// As the weight of a node acts as the heuristic of how far it lies from the goal, the
+/// estimate compares the weight of the best node expanded so far against the weight of
+/// the start node, giving `1.0 - best / start` clamped to `[0.0, 1.0]`. It is reported each
+/// time a node is expanded, never decreases and ends on `1.0` once a path is found. It is
+/// only approximate, the search may stall on a plateau of equal weights or jump forward in
+/// one step. When the start node has no weight nothing can be inferred and `0.0` is
+/// reported until the path is found.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_with_listener, ProgressListener};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 2.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 1.0));
+/// nodes.insert(2, (vec![], 0.0));
+/// let mut reported = Vec::new();
+/// let mut listener = ProgressListener::new(0, &nodes, |fraction| reported.push(fraction));
+/// let path = astar_path_with_listener(0, &nodes, 2, &mut listener);
+/// assert_eq!(Some(vec![0, 1, 2]), path);
+/// assert_eq!(vec![0.0, 0.5, 1.0], reported);
+/// ```
+#[allow(clippy::type_complexity)]
+pub struct ProgressListener<'a, T, P> {
+	/// The graph being searched, for the weight of each node expanded
+	nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>,
+	/// Weight of the start node which the weight of each node expanded is compared against
+	start_weight: f32,
+	/// The furthest estimate reported so far, holding onto it keeps the estimate steady
+	/// when the head of the queue falls back to a node further from the goal
+	reported: f32,
+	/// Called with each estimate
+	progress: P,
+}
+
+impl<'a, T, P> ProgressListener<'a, T, P>
+where
+	T: Eq + Hash,
+	P: FnMut(f32),
+{
+	/// A listener for a search of `nodes` from `start_node` which calls `progress` with
+	/// each estimate
+	#[allow(clippy::type_complexity)]
+	pub fn new(start_node: T, nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>, progress: P) -> Self {
+		ProgressListener {
+			nodes,
+			start_weight: nodes.get(&start_node).map_or(0.0, |x| x.1),
+			reported: 0.0,
+			progress,
+		}
+	}
+}
+
+impl<T, P> SearchListener<T> for ProgressListener<'_, T, P>
+where
+	T: Eq + Hash,
+	P: FnMut(f32),
+{
+	fn on_expand(&mut self, node: &T, _score: f32, _distance: f32) {
+		if self.start_weight > 0.0 {
+			let weight = self.nodes.get(node).map_or(self.start_weight, |x| x.1);
+			let estimate = (1.0 - weight / self.start_weight).clamp(0.0, 1.0);
+			if estimate > self.reported {
+				self.reported = estimate;
+			}
+		}
+		(self.progress)(self.reported);
+	}
+	fn on_finish(&mut self, path: &Option<Vec<T>>) {
+		if path.is_some() {
+			self.reported = 1.0;
+			(self.progress)(self.reported);
+		}
+	}
+}
+
+/// Behaves like [astar_path] but allows travel along an edge to cost differently in each
+/// direction, such as climbing up a slope versus descending it.
+///
+/// The weight of each node in `nodes` is its enter weight, the cost of stepping onto it.
+/// The distance of the edge from `a` to `b` is scaled by the multiplier stored in
+/// `multipliers` under the key `(a, b)`, edges without an entry use a multiplier of `1.0`.
+/// As the reverse edge from `b` to `a` has its own key the two directions can differ.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_directional;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 2.0), (2, 2.0)], 0.0));
+/// nodes.insert(1, (vec![(0, 2.0), (2, 2.0)], 0.0));
+/// nodes.insert(2, (vec![(0, 2.0), (1, 2.0)], 0.0));
+/// let mut multipliers = HashMap::new();
+/// // 0 sits at the bottom of a hill with 1 at the top
+/// multipliers.insert((0, 1), 3.0);
+/// multipliers.insert((1, 0), 0.5);
+/// assert_eq!(Some(vec![1, 0]), astar_path_directional(1, &nodes, 0, &multipliers));
+/// assert_eq!(Some(vec![0, 2, 1]), astar_path_directional(0, &nodes, 1, &multipliers));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path], which includes a negative multiplier giving
+/// a negative distance
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_directional<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	multipliers: &HashMap<(T, T), f32>,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let neighbours = stored_neighbours(nodes);
+	search(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| {
+			let node = *node;
+			Ok(neighbours(&node)?.map(move |(neighbour, distance)| {
+				let multiplier = multipliers.get(&(node, neighbour)).unwrap_or(&1.0);
+				(neighbour, distance * multiplier)
+			}))
+		},
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()
+}
+
+/// Behaves like [astar_path] except that the weight of a node counts for more the further
+/// a route has traveled to reach it, modelling fatigue where difficult terrain becomes
+/// harder to cross when tired.
+///
+/// A route which has traveled `distance` to reach a node of `weight` is scored as
+/// `distance + weight * (1.0 + fatigue_rate * distance)`, so with a `fatigue_rate` of `0.0`
+/// this behaves exactly as [astar_path]. Positive rates favour routes which tackle
+/// difficult nodes early on while fresh.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path, astar_path_fatigue};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// assert_eq!(astar_path(0, &nodes, 2), astar_path_fatigue(0, &nodes, 2, 0.0));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_fatigue<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	fatigue_rate: f32,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		stored_neighbours(nodes),
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+		&mut NoListener,
+		|_| (),
+		usize::MAX,
+		f32::INFINITY,
+		|distance, weight| distance + weight * (1.0 + fatigue_rate * distance),
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()
+}
+
+/// Behaves like [astar_path] but drops any route which travels further than `upper_bound`
+/// as soon as it's discovered, rather than queuing it. When an acceptable bound is already
+/// known, such as the distance of a previously cached path, this keeps the search from
+/// exploring routes which could never be used.
+///
+/// Returns `None` if no path to `end_node` travels a distance within `upper_bound`.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_with_bound;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// assert_eq!(Some(vec![0, 1, 2]), astar_path_with_bound(0, &nodes, 2, 9.0));
+/// assert_eq!(None, astar_path_with_bound(0, &nodes, 2, 8.0));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_bound<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	upper_bound: f32,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		stored_neighbours(nodes),
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+		&mut NoListener,
+		|_| (),
+		usize::MAX,
+		upper_bound,
+		a_star_score,
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()
+}
+
+/// Behaves like [astar_path] but only considers paths made of at most `max_hops` edges,
+/// returning the best of those, such as the movement range of a turn-based unit. The best
+/// path within the limit may cost more than the best path overall.
+///
+/// Routes are tracked by the node they reach along with the number of hops taken, so a
+/// costlier route reaching a node in fewer hops is still explored, as it may be the only
+/// one able to continue within the limit. Returns `None` if `end_node` can't be reached in
+/// `max_hops` or fewer.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_max_hops;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (3, 5.0)], 1.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// assert_eq!(Some(vec![0, 1, 2, 3]), astar_path_max_hops(0, &nodes, 3, 3));
+/// assert_eq!(Some(vec![0, 3]), astar_path_max_hops(0, &nodes, 3, 2));
+/// assert_eq!(None, astar_path_max_hops(0, &nodes, 3, 0));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_max_hops<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	max_hops: usize,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let neighbours = stored_neighbours(nodes);
+	let weight = stored_weight(nodes, |_, weight| weight);
+	// search over each node paired with the hops taken to reach it
+	search(
+		(start_node, 0),
+		|(node, _), _, _| *node == end_node,
+		|(node, hops)| {
+			let hops = *hops;
+			let within = hops < max_hops;
+			Ok(neighbours(node)
+				.map_err(|e| e.map_nodes(|node| (node, hops)))?
+				.filter(move |_| within)
+				.map(move |(neighbour, distance)| ((neighbour, hops + 1), distance)))
+		},
+		|(node, hops)| weight(node).map_err(|e| e.map_nodes(|node| (node, *hops))),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e.map_nodes(|(node, _)| node)))
+	.into_option()
+	.map(|path| path.into_iter().map(|(node, _)| node).collect())
+}
+
+/// Behaves like [astar_path] but adds a penalty for uneven steps to the distance traveled,
+/// so paths whose consecutive edges have similar distances are preferred for smooth
+/// movement without sudden long jumps.
+///
+/// Each step is penalised by `smoothness_weight` times its [path_smoothness] contribution,
+/// the squared difference between its distance and that of the step before. This is a soft
+/// preference layered on the cost: a weight of `0.0` behaves as [astar_path] while larger
+/// weights accept a longer path in exchange for more uniform steps. Routes are tracked by
+/// the node they reach along with the distance of the step into it, as the penalty of the
+/// next step depends on it.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_smooth;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// // a short hop then a long jump, or two even steps which are slightly longer overall
+/// nodes.insert(0, (vec![(1, 1.0), (2, 3.0)], 0.0));
+/// nodes.insert(1, (vec![(3, 4.5)], 0.0));
+/// nodes.insert(2, (vec![(3, 3.0)], 0.0));
+/// nodes.insert(3, (vec![], 0.0));
+/// assert_eq!(Some(vec![0, 1, 3]), astar_path_smooth(0, &nodes, 3, 0.0));
+/// assert_eq!(Some(vec![0, 2, 3]), astar_path_smooth(0, &nodes, 3, 0.1));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_smooth<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	smoothness_weight: f32,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let neighbours = stored_neighbours(nodes);
+	let weight = stored_weight(nodes, |_, weight| weight);
+	// search over each node paired with the bits of the distance of the step into it
+	search(
+		(start_node, None),
+		|(node, _), _, _| *node == end_node,
+		|(node, previous): &(T, Option<u32>)| {
+			let previous = previous.map(f32::from_bits);
+			Ok(neighbours(node)
+				.map_err(|e| e.map_nodes(|node| (node, None)))?
+				.map(move |(neighbour, distance)| {
+					let penalty = previous.map_or(0.0, |previous| {
+						smoothness_weight * step_penalty(previous, distance)
+					});
+					((neighbour, Some(distance.to_bits())), distance + penalty)
+				}))
+		},
+		|(node, previous)| weight(node).map_err(|e| e.map_nodes(|node| (node, *previous))),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e.map_nodes(|(node, _)| node)))
+	.into_option()
+	.map(|path| path.into_iter().map(|(node, _)| node).collect())
+}
+
+/// Behaves like [astar_path] with the path confined to the `allowed` nodes, as though
+/// every other node were removed, such as keeping scripted movement to roads.
+///
+/// This is the complement of blocking nodes with [AstarOptions], and
+/// is simpler to describe when the permitted region is the smaller of the two. Both
+/// `start_node` and `end_node` must be allowed, otherwise `None` is returned.
+///
+/// ```rust
+/// use std::collections::{HashMap, HashSet};
+/// use pathfinding_astar::astar_path_within;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 5.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// let roads: HashSet<i32> = [0, 2, 3].iter().copied().collect();
+/// assert_eq!(Some(vec![0, 2, 3]), astar_path_within(0, &nodes, 3, &roads));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_within<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	allowed: &HashSet<T>,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	if !allowed.contains(&start_node) || !allowed.contains(&end_node) {
+		return None;
+	}
+	let neighbours = stored_neighbours(nodes);
+	search(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| Ok(neighbours(node)?.filter(|(neighbour, _)| allowed.contains(neighbour))),
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()
+}
+
+/// Behaves like [astar_path] for a graph whose edges carry a label, such as the name of
+/// the road or route they belong to, reporting which edge was taken to reach each node.
+///
+/// Each neighbour is given as `(neighbour_label, distance, edge_label)`. The path pairs
+/// every node with the label of the edge used to reach it, the start node has no such edge
+/// so is paired with `None`. Where several edges join the same two nodes the shortest is
+/// taken, if they tie on distance the first listed is reported.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_with_edge_labels;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32, &str)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0, "Route 5")], 1.0));
+/// nodes.insert(1, (vec![(2, 4.0, "Route 9")], 1.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// let path = astar_path_with_edge_labels(0, &nodes, 2).unwrap();
+/// assert_eq!(vec![(0, None), (1, Some("Route 5")), (2, Some("Route 9"))], path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_edge_labels<T, E>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32, E)>, f32)>,
+	end_node: T,
+) -> Option<Vec<(T, Option<E>)>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	E: Clone,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let path = search(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| match nodes.get(node) {
+			Some(x) => Ok(x
+				.0
+				.iter()
+				.map(|(neighbour, distance, _)| (*neighbour, *distance))),
+			None => Err(AstarError::MissingNode(*node)),
+		},
+		|node| match nodes.get(node) {
+			Some(x) => Ok((x.1, x.1)),
+			None => Err(AstarError::MissingNode(*node)),
+		},
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()?;
+	let mut labelled = vec![(start_node, None)];
+	for step in path.windows(2) {
+		// the search kept the shortest of any parallel edges
+		let mut taken: Option<(f32, &E)> = None;
+		for (neighbour, distance, label) in nodes[&step[0]].0.iter() {
+			if *neighbour == step[1] && taken.map_or(true, |(shortest, _)| *distance < shortest) {
+				taken = Some((*distance, label));
+			}
+		}
+		labelled.push((step[1], taken.map(|(_, label)| label.clone())));
+	}
+	Some(labelled)
+}
+
+/// Behaves like [astar_path] but returns the distance of each edge traveled along the path
+/// rather than the nodes, for auditing what each hop costs. There is one distance fewer
+/// than there are nodes in the path, so a path which starts at its goal gives an empty
+/// list. Where several edges join the same two nodes the shortest is reported, as it is
+/// the one the search travels along. See [format_path_with_costs] for the running total
+/// instead.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_edge_costs;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// assert_eq!(Some(vec![5.0, 4.0]), astar_path_edge_costs(0, &nodes, 2));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_edge_costs<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<Vec<f32>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let path = astar_path(start_node, nodes, end_node)?;
+	// a hop without an edge gives `None` rather than a list missing that hop
+	path.windows(2)
+		.map(|step| graph::edge_distance(nodes, &step[0], &step[1]))
+		.collect()
+}
+
+/// Behaves like [astar_path] for a graph whose edges have an uncertain distance, trading
+/// the expected distance of a route against how reliable it is.
+///
+/// Each neighbour is given as `(neighbour_label, mean, variance)` and the edge is traveled
+/// as a distance of `mean + risk * sqrt(variance)`. A `risk` of `0.0` routes on the means
+/// alone while larger values increasingly avoid edges whose distance varies widely.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_risk_averse;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 2.0, 16.0), (2, 3.0, 0.0)], 1.0));
+/// nodes.insert(1, (vec![], 1.0));
+/// nodes.insert(2, (vec![(1, 1.0, 0.0)], 1.0));
+/// assert_eq!(Some(vec![0, 1]), astar_path_risk_averse(0, &nodes, 1, 0.0));
+/// assert_eq!(Some(vec![0, 2, 1]), astar_path_risk_averse(0, &nodes, 1, 1.0));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path], a negative variance can't be scored and is
+/// treated as a `NaN` distance
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_risk_averse<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32, f32)>, f32)>,
+	end_node: T,
+	risk: f32,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	search(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| match nodes.get(node) {
+			Some(x) => Ok(x.0.iter().map(move |(neighbour, mean, variance)| {
+				(*neighbour, mean + risk * variance.sqrt())
+			})),
+			None => Err(AstarError::MissingNode(*node)),
+		},
+		|node| match nodes.get(node) {
+			Some(x) => Ok((x.1, x.1)),
+			None => Err(AstarError::MissingNode(*node)),
+		},
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()
+}
+
+/// Behaves like [astar_path] but never holds more than `max_frontier` routes awaiting
+/// processing, for targets where memory is tight.
+///
+/// After each node is expanded only the best `max_frontier` routes are kept and the rest
+/// are dropped, a form of beam search expressed as an absolute limit. This trades away
+/// completeness and optimality for bounded memory: the path found may not be the most
+/// optimal and `None` may be returned even though a path exists, when every route leading
+/// to the end node was dropped. Larger caps behave more like [astar_path], a cap of `0` is
+/// treated as `1`.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::astar_path_capped_frontier;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// assert_eq!(Some(vec![0, 1, 2]), astar_path_capped_frontier(0, &nodes, 2, 1));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_capped_frontier<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	max_frontier: usize,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_path_capped_frontier_with_stats(start_node, nodes, end_node, max_frontier).0
+}
+
+/// Behaves like [astar_path_capped_frontier] but also reports statistics of the search as
+/// per [astar_path_with_stats]
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_capped_frontier_with_stats<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	max_frontier: usize,
+) -> (Option<Vec<T>>, SearchStats)
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let mut stats = SearchStats::default();
+	let path = search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		stored_neighbours(nodes),
+		stored_weight(nodes, |_, weight| weight),
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut stats,
+		&mut NoListener,
+		|_| (),
+		max_frontier.max(1),
+		f32::INFINITY,
+		a_star_score,
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option();
+	(path, stats)
+}
+
+/// Runs the core search against a `nodes` data set as per [try_astar_search], counting the
+/// work carried out along the way
+///
+/// # Panics
+///
+/// If the search fails with an [AstarError]
+#[allow(clippy::type_complexity)]
+pub(crate) fn astar_search_with_stats<T, W>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	effective_weight: W,
+) -> (PathResult<T>, SearchStats)
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	W: Fn(&T, f32) -> f32,
+{
+	let mut stats = SearchStats::default();
+	let result = try_astar_search(
+		start_node,
+		nodes,
+		end_node,
+		effective_weight,
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut stats,
+	)
+	.unwrap_or_else(|e| panic!("{}", e));
+	(result, stats)
+}
+
+/// Behaves like [astar_path] except that the search gives up once `timeout` has elapsed,
+/// returning [PathResult::Timeout].
+///
+/// The elapsed time is checked every [DEFAULT_TIMEOUT_CHECK_INTERVAL] node expansions,
+/// use [astar_path_timed_with_interval] to tune how often `Instant::now()` is called.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use std::time::Duration;
+/// use pathfinding_astar::{astar_path_timed, PathResult};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let result = astar_path_timed(0, &nodes, 2, Duration::from_secs(1));
+/// assert_eq!(PathResult::Found(vec![0, 1, 2]), result);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_timed<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	timeout: Duration,
+) -> PathResult<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	astar_path_timed_with_interval(
+		start_node,
+		nodes,
+		end_node,
+		timeout,
+		DEFAULT_TIMEOUT_CHECK_INTERVAL,
+	)
+}
+
+/// Behaves like [astar_path_timed] with the elapsed time being checked every
+/// `check_interval` node expansions. A `check_interval` of `0` is treated as `1`.
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path]
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_timed_with_interval<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	timeout: Duration,
+	check_interval: usize,
+) -> PathResult<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	let check_interval = check_interval.max(1);
+	let started = Instant::now();
+	astar_search(
+		start_node,
+		nodes,
+		end_node,
+		|_, weight| weight,
+		DEFAULT_SCORE_EPSILON,
+		|expansions| {
+			if expansions % check_interval == 0 && started.elapsed() >= timeout {
+				Some(PathResult::Timeout)
+			} else {
+				None
+			}
+		},
+	)
+}
+
+/// Finds the most optimal path from `start_node` to `end_node` without requiring a map
+/// of nodes, instead the graph is discovered lazily as the search progresses.
+///
+/// This suits effectively infinite or implicit graphs, such as open-world maps, which
+/// can't be materialised up front:
+/// * `neighbours_fn` produces the `(neighbour_label, distance_to_neighbour)` pairs of a node
+/// * `weight_fn` produces the weight of a node, the heuristic guiding the search
+///
+/// As the graph may be unbounded the search gives up with [PathResult::ExpansionLimit]
+/// once `max_expansions` nodes have been expanded without reaching `end_node`.
+///
+/// ```rust
+/// use pathfinding_astar::{astar_path_lazy, PathResult};
+///
+/// // an infinite line of integers where each can step to its neighbours either side
+/// let end = 3;
+/// let result = astar_path_lazy(
+///     0,
+///     end,
+///     |n: &i32| vec![(n - 1, 1.0), (n + 1, 1.0)],
+///     |n: &i32| (end - n).abs() as f32,
+///     100,
+/// );
+/// assert_eq!(PathResult::Found(vec![0, 1, 2, 3]), result);
+/// ```
+///
+/// # Panics
+///
+/// If `weight_fn` or `neighbours_fn` produce a `NaN` weight or distance, or a negative
+/// distance
+#[must_use = "the computed path is discarded if unused"]
+pub fn astar_path_lazy<T, N, NI, W>(
+	start_node: T,
+	end_node: T,
+	neighbours_fn: N,
+	weight_fn: W,
+	max_expansions: usize,
+) -> PathResult<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	N: Fn(&T) -> NI,
+	NI: IntoIterator<Item = (T, f32)>,
+	W: Fn(&T) -> f32,
+{
+	search(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| Ok(neighbours_fn(node)),
+		|node| {
+			let weight = weight_fn(node);
+			Ok((weight, weight))
+		},
+		DEFAULT_SCORE_EPSILON,
+		|expansions| {
+			if expansions >= max_expansions {
+				Some(PathResult::ExpansionLimit)
+			} else {
+				None
+			}
+		},
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Behaves like [astar_path] for a graph whose structure is stored apart from its metric.
+///
+/// `adjacency` lists the neighbours of each node without any distances, instead the
+/// distance between a node and its neighbour is computed on demand by `dist`. This allows
+/// the cost of travel to change at runtime without rebuilding the graph. `weights` holds
+/// the weight of each node.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_topo, CostMap};
+///
+/// let mut adjacency: HashMap<i32, Vec<i32>> = HashMap::new();
+/// adjacency.insert(0, vec![1, 2]);
+/// adjacency.insert(1, vec![3]);
+/// adjacency.insert(2, vec![3]);
+/// adjacency.insert(3, vec![]);
+/// let weights: CostMap<i32> = (0..4).map(|n| (n, 1.0)).collect();
+/// // travel to odd numbered nodes is slow
+/// let dist = |_: &i32, to: &i32| if to % 2 == 1 { 5.0 } else { 1.0 };
+/// let path = astar_path_topo(0, &adjacency, &weights, 3, dist).unwrap();
+/// assert_eq!(vec![0, 2, 3], path);
+/// ```
+///
+/// # Panics
+///
+/// If `adjacency` doesn't contain `start_node` or `end_node`, a neighbour isn't a key of
+/// both `adjacency` and `weights` or `dist` produces a `NaN` or negative distance
+#[must_use = "the computed path is discarded if unused"]
+pub fn astar_path_topo<T, D>(
+	start_node: T,
+	adjacency: &HashMap<T, Vec<T>>,
+	weights: &CostMap<T>,
+	end_node: T,
+	dist: D,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	D: Fn(&T, &T) -> f32,
+{
+	// ensure the adjacency contains start and end points
+	check_endpoints(adjacency, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let dist = &dist;
+	search(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| match adjacency.get(node) {
+			Some(x) => {
+				let node = *node;
+				Ok(x.iter().map(move |n| (*n, dist(&node, n))))
+			}
+			None => Err(AstarError::MissingNode(*node)),
+		},
+		|node| match weights.get(node) {
+			Some(weight) => Ok((*weight, *weight)),
+			None => Err(AstarError::MissingNode(*node)),
+		},
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option()
+}
+
+/// Runs the core search against a `nodes` data set as per [try_astar_search]
+///
+/// # Panics
+///
+/// If the search fails with an [AstarError]
+#[allow(clippy::type_complexity)]
+pub(crate) fn astar_search<T, W, I>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	effective_weight: W,
+	epsilon: f32,
+	interrupt: I,
+) -> PathResult<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	W: Fn(&T, f32) -> f32,
+	I: FnMut(usize) -> Option<PathResult<T>>,
+{
+	try_astar_search(
+		start_node,
+		nodes,
+		end_node,
+		effective_weight,
+		epsilon,
+		interrupt,
+		&mut SearchStats::default(),
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Runs the core search against a `nodes` data set, ensuring that it contains the start
+/// and end points before handing lookups of neighbours and weights to [search].
+///
+/// `effective_weight` is handed a node label along with its stored weight and returns the
+/// effective weight used for scoring, allowing callers to adjust weights at query time
+/// without modifying `nodes`. The work carried out is recorded in `stats`
+#[allow(clippy::type_complexity)]
+fn try_astar_search<T, W, I>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	effective_weight: W,
+	epsilon: f32,
+	interrupt: I,
+	stats: &mut SearchStats,
+) -> Result<PathResult<T>, AstarError<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	W: Fn(&T, f32) -> f32,
+	I: FnMut(usize) -> Option<PathResult<T>>,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node)?;
+	search(
+		start_node,
+		|node, _, _| *node == end_node,
+		stored_neighbours(nodes),
+		stored_weight(nodes, effective_weight),
+		epsilon,
+		interrupt,
+		stats,
+	)
+}
+
+/// Checks that `nodes` contains both `start_node` and `end_node`, failing with
+/// [AstarError::MissingStartNode] or [AstarError::MissingEndNode] for the first missing
+pub(crate) fn check_endpoints<T, V>(
+	nodes: &HashMap<T, V>,
+	start_node: T,
+	end_node: T,
+) -> Result<(), AstarError<T>>
+where
+	T: Eq + Hash,
+{
+	if !nodes.contains_key(&start_node) {
+		return Err(AstarError::MissingStartNode(start_node));
+	}
+	if !nodes.contains_key(&end_node) {
+		return Err(AstarError::MissingEndNode(end_node));
+	}
+	Ok(())
+}
+
+/// Produces a lookup of the neighbours of a node stored in `nodes` for use with [search],
+/// failing with [AstarError::MissingNode] if a node is not a key of `nodes`
+#[allow(clippy::type_complexity)]
+pub(crate) fn stored_neighbours<'a, T>(
+	nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>,
+) -> impl Fn(&T) -> Result<std::iter::Copied<std::slice::Iter<'a, (T, f32)>>, AstarError<T>> + 'a
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	move |node| match nodes.get(node) {
+		Some(x) => Ok(x.0.iter().copied()),
+		None => Err(AstarError::MissingNode(*node)),
+	}
+}
+
+/// Produces a lookup of the weight of a node stored in `nodes` for use with [search],
+/// giving the weight passed through `effective_weight` to allow adjustments at query time
+/// alongside the stored weight. Fails with [AstarError::MissingNode] if a node is not a key
+/// of `nodes`
+#[allow(clippy::type_complexity)]
+pub(crate) fn stored_weight<'a, T, W>(
+	nodes: &'a HashMap<T, (Vec<(T, f32)>, f32)>,
+	effective_weight: W,
+) -> impl Fn(&T) -> Result<(f32, f32), AstarError<T>> + 'a
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	W: Fn(&T, f32) -> f32 + 'a,
+{
+	move |node| match nodes.get(node) {
+		Some(x) => Ok((effective_weight(node, x.1), x.1)),
+		None => Err(AstarError::MissingNode(*node)),
+	}
+}
+
+/// Core of the A-Star search shared by the public entry points.
+///
+/// `is_goal` is called each time a node reaches the head of the queue, along with the
+/// [Route] to it and the distance traveled, returning `true` ends the search with that node
+/// as the end of the path. The first time a node reaches the head of the
+/// queue is the point at which a search for it would complete, the routes remaining in the
+/// queue are left unexplored.
+///
+/// `neighbours` produces the `(neighbour_label, distance_to_neighbour)` pairs of a node
+/// and `weight` the `(effective_weight, stored_weight)` of a node, the first is used to score
+/// it while the second breaks ties, allowing the graph to be stored in a map or generated on
+/// the fly. Either lookup failing ends the search with that error.
+///
+/// `epsilon` is the tolerance within which two scores are considered equal when deciding
+/// whether a newly discovered route should replace a previously recorded one.
+///
+/// `interrupt` is called before each node is expanded with the total number of
+/// expansions so far, returning `Some` abandons the search with the given result.
+///
+/// The frontier holds at most one live route per node, replacing a route pushes its
+/// successor onto the heap and leaves the old entry to be discarded when it surfaces.
+/// Each queued route records only the node it was discovered from, the node each node was
+/// expanded from is recorded as it is expanded and the path is walked back along this
+/// predecessor chain once a goal is found. The work carried out is recorded in `stats`.
+///
+/// Any route scoring `NaN` fails the search with [AstarError::NanRoute] before it is queued,
+/// ensuring the queue can always be ordered, while a start node with a `NaN` weight fails
+/// with [AstarError::NanScore]
+pub(crate) fn search<T, G, N, NI, W, I>(
+	start_node: T,
+	is_goal: G,
+	neighbours: N,
+	weight: W,
+	epsilon: f32,
+	interrupt: I,
+	stats: &mut SearchStats,
+) -> Result<PathResult<T>, AstarError<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	G: FnMut(&T, &Route<'_, T>, f32) -> bool,
+	N: FnMut(&T) -> Result<NI, AstarError<T>>,
+	NI: IntoIterator<Item = (T, f32)>,
+	W: FnMut(&T) -> Result<(f32, f32), AstarError<T>>,
+	I: FnMut(usize) -> Option<PathResult<T>>,
+{
+	search_by(
+		start_node,
+		is_goal,
+		neighbours,
+		weight,
+		epsilon,
+		interrupt,
+		stats,
+		&mut NoListener,
+		|_| (),
+		usize::MAX,
+		f32::INFINITY,
+		a_star_score,
+		TieBreak::DistanceTraveled,
+	)
+}
+
+/// Behaves like [search] except that routes which tie on score, distance and stored weight
+/// are ordered by their `rank` before falling back on the order in which they were queued.
+/// `rank` is handed the [Route] being queued, the start node being ranked by the route made
+/// up of itself alone. As the frontier holds one route per node, a `rank` which is unique
+/// per node, such as [Route::node], makes the order of expansion independent of the order in
+/// which neighbours are listed. A route tying with the route already queued to its node only
+/// replaces it when it ranks no worse, so a `rank` of the whole route, such as the number
+/// of turns it makes, settles which of several equal routes into a node is kept.
+///
+/// After each expansion the queue is cut down to the best `frontier_cap` routes, the rest
+/// are dropped and never explored. Routes whose distance traveled exceeds `max_distance`
+/// are never queued.
+///
+/// `listener` is told of each node expanded and each route queued.
+///
+/// `score` combines the distance traveled along a route with the effective weight of the
+/// node it reaches into the A-Star score of the route, [search] uses [a_star_score]
+///
+/// `tie_break` sets whether routes tying on score are ordered by the distance they have
+/// traveled before their stored weight, [search] uses [TieBreak::DistanceTraveled]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn search_by<T, G, N, NI, W, I, L, R, K, S>(
+	start_node: T,
+	is_goal: G,
+	neighbours: N,
+	mut weight: W,
+	epsilon: f32,
+	interrupt: I,
+	stats: &mut SearchStats,
+	listener: &mut L,
+	mut rank: R,
+	frontier_cap: usize,
+	max_distance: f32,
+	score: S,
+	tie_break: TieBreak,
+) -> Result<PathResult<T>, AstarError<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	G: FnMut(&T, &Route<'_, T>, f32) -> bool,
+	N: FnMut(&T) -> Result<NI, AstarError<T>>,
+	NI: IntoIterator<Item = (T, f32)>,
+	W: FnMut(&T) -> Result<(f32, f32), AstarError<T>>,
+	I: FnMut(usize) -> Option<PathResult<T>>,
+	L: SearchListener<T>,
+	R: FnMut(&Route<'_, T>) -> K,
+	K: Ord + Clone,
+	S: FnMut(f32, f32) -> f32,
+{
+	let mut state = SearchState::new(start_node, &mut weight, &mut rank, tie_break)?;
+	stats.max_frontier = stats.max_frontier.max(state.queue.len());
+	resume_by(
+		&mut state,
+		is_goal,
+		neighbours,
+		weight,
+		epsilon,
+		interrupt,
+		stats,
+		listener,
+		rank,
+		frontier_cap,
+		max_distance,
+		score,
+	)
+}
+
+/// The progress of a search carried out by [resume_by], which can be continued later
+#[derive(Debug, Clone)]
+pub(crate) struct SearchState<T, K> {
+	/// Every time we process a new node we add it to a map along with the distance traveled to it.
+	/// If a node has already been recorded then we replace it if it has a better a-star score (smaller number)
+	/// otherwise we discard it.
+	/// This is used to optimise the searching whereby if we find a new path to a previously
+	/// processed node we can quickly decide to discard or explore the new route
+	node_astar_scores: HashMap<T, (f32, f32)>,
+	/// Queue of nodes to be processed based on discovery
+	queue: Frontier<T, K>,
+	/// Nodes which have already been expanded, a route to one of these is only explored again
+	/// if it strictly improves upon its score. Otherwise a cycle of routes which tie, such as a
+	/// zero distance edge from a node to itself, would be expanded over and over
+	expanded: HashSet<T>,
+	/// The node each node was last expanded from, along with that of a goal found at the head
+	/// of the queue. The start node has no entry
+	predecessors: HashMap<T, T>,
+	/// Node the search began from, where every predecessor chain ends
+	start_node: T,
+	/// How routes which tie on score are ordered
+	tie_break: TieBreak,
+}
+
+impl<T, K> SearchState<T, K>
+where
+	T: Eq + Hash + Copy,
+	K: Ord + Clone,
+{
+	/// A search which has yet to expand `start_node`, the only node queued, ordering routes
+	/// which tie on score as set by `tie_break`
+	fn new<W, R>(
+		start_node: T,
+		weight: &mut W,
+		rank: &mut R,
+		tie_break: TieBreak,
+	) -> Result<Self, AstarError<T>>
+	where
+		W: FnMut(&T) -> Result<(f32, f32), AstarError<T>>,
+		R: FnMut(&Route<'_, T>) -> K,
+	{
+		// retreive the weight of the start point, it doesn't contribute to the initial score but
+		// still needs to be rankable should a route return to the start
+		let (start_weight, start_stored_weight) = weight(&start_node)?;
+		if start_weight.is_nan() || start_stored_weight.is_nan() {
+			return Err(AstarError::NanScore(start_node));
+		}
+		// add starting node a-star score to data set, the weight of the node a route begins on
+		// doesn't count against it so the score starts at zero
+		let mut node_astar_scores = HashMap::new();
+		node_astar_scores.insert(start_node, (0.0, 0.0));
+		// start by adding the starting node to the queue
+		let mut queue = Frontier::new();
+		let predecessors = HashMap::new();
+		let start_rank = rank(&Route {
+			node: start_node,
+			from: None,
+			start_node,
+			predecessors: &predecessors,
+		});
+		queue.push(QueueEntry {
+			node: start_node,
+			score: 0.0, // we haven't moved and the start weight isn't counted so the score is zero
+			from: None,
+			distance: 0.0,
+			lead: 0.0,
+			stored_weight: start_stored_weight,
+			rank: start_rank,
+			id: 0,
+		});
+		Ok(SearchState {
+			node_astar_scores,
+			queue,
+			expanded: HashSet::new(),
+			predecessors,
+			start_node,
+			tie_break,
+		})
+	}
+	/// The path from the start node to `node` along the chain of predecessors recorded so far
+	fn path_to(&self, node: T) -> Vec<T> {
+		Route {
+			node,
+			from: self.predecessors.get(&node).copied(),
+			start_node: self.start_node,
+			predecessors: &self.predecessors,
+		}
+		.path()
+	}
+	/// Scores every node recorded, and every route queued, afresh by combining the distance
+	/// traveled to it with its effective `weight` through `score`, so the search can carry on
+	/// under new weights such as those inflated by a smaller factor. Distances traveled are
+	/// unchanged so the predecessors recorded still hold and nodes already expanded needn't
+	/// be expanded again, only the order in which queued routes are explored changes.
+	///
+	/// This is the step ARA* takes between iterations. A node reached more cheaply after it
+	/// was expanded has already been queued again by [advance_by], so the queue holds every
+	/// node ARA* would merge back in from its list of inconsistent nodes
+	fn rescore<W, S>(&mut self, mut weight: W, mut score: S) -> Result<(), AstarError<T>>
+	where
+		W: FnMut(&T) -> Result<(f32, f32), AstarError<T>>,
+		S: FnMut(f32, f32) -> f32,
+	{
+		for (node, (node_score, distance)) in self.node_astar_scores.iter_mut() {
+			// the weight of the start node never counts against a route so it keeps its score
+			if *node != self.start_node {
+				*node_score = score(*distance, weight(node)?.0);
+			}
+		}
+		let node_astar_scores = &self.node_astar_scores;
+		self.queue
+			.rescore(|node| match node_astar_scores.get(node) {
+				Some((node_score, _)) => *node_score,
+				None => unreachable!("every queued node has a recorded score"),
+			});
+		Ok(())
+	}
+}
+
+/// A route to a node handed to the `is_goal` and `rank` closures of [search_by], giving the
+/// node it reaches and the node it was discovered from. The rest of the route is held as
+/// the chain of predecessors recorded by the search, it is only walked to build the path
+/// when [Route::path] is called
+pub(crate) struct Route<'a, T> {
+	/// The node the route reaches
+	node: T,
+	/// The node the route was discovered from, `None` for the start node
+	from: Option<T>,
+	/// Node the search began from, where the chain of predecessors ends
+	start_node: T,
+	/// The node each expanded node was expanded from
+	predecessors: &'a HashMap<T, T>,
+}
+
+impl<'a, T> Route<'a, T>
+where
+	T: Eq + Hash + Copy,
+{
+	/// The node the route reaches
+	pub(crate) fn node(&self) -> T {
+		self.node
+	}
+	/// The node the route was discovered from, `None` for the start node
+	pub(crate) fn from(&self) -> Option<T> {
+		self.from
+	}
+	/// The node the route passed through before the node it was discovered from, `None` if
+	/// the route is the start node alone or was discovered from the start node
+	pub(crate) fn previous(&self) -> Option<T> {
+		self.ancestors().nth(1)
+	}
+	/// Walks the chain of predecessors back to the start node, giving the path from the start
+	/// node through to the node the route reaches inclusive
+	pub(crate) fn path(&self) -> Vec<T> {
+		let mut path = vec![self.node];
+		path.extend(self.ancestors());
+		path.reverse();
+		path
+	}
+	/// Number of nodes along the route, including the start node and the node it reaches,
+	/// counted by walking the chain of predecessors without building the path
+	pub(crate) fn len(&self) -> usize {
+		1 + self.ancestors().count()
+	}
+	/// The nodes the route passes through before the node it reaches, walking back from the
+	/// node it was discovered from to the start node
+	fn ancestors(&self) -> impl Iterator<Item = T> + '_ {
+		let mut next = self.from;
+		std::iter::from_fn(move || {
+			let node = next?;
+			next = if node == self.start_node {
+				None
+			} else {
+				self.predecessors.get(&node).copied()
+			};
+			Some(node)
+		})
+	}
+}
+
+/// How a call to [advance_by] came to a halt
+pub(crate) enum Halt<T> {
+	/// The node, along with the distance traveled to it, at the head of the queue which
+	/// `is_goal` accepted. Its predecessor is recorded so the path to it can be walked
+	Goal(T, f32),
+	/// The search ran out of routes or was interrupted with the given result
+	Ended(PathResult<T>),
+}
+
+/// Continues the search recorded in `state` as per [search_by]. A goal found is left at the
+/// head of the queue so that resuming again returns it straight away
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resume_by<T, G, N, NI, W, I, L, R, K, S>(
+	state: &mut SearchState<T, K>,
+	is_goal: G,
+	neighbours: N,
+	weight: W,
+	epsilon: f32,
+	interrupt: I,
+	stats: &mut SearchStats,
+	listener: &mut L,
+	rank: R,
+	frontier_cap: usize,
+	max_distance: f32,
+	score: S,
+) -> Result<PathResult<T>, AstarError<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	G: FnMut(&T, &Route<'_, T>, f32) -> bool,
+	N: FnMut(&T) -> Result<NI, AstarError<T>>,
+	NI: IntoIterator<Item = (T, f32)>,
+	W: FnMut(&T) -> Result<(f32, f32), AstarError<T>>,
+	I: FnMut(usize) -> Option<PathResult<T>>,
+	L: SearchListener<T>,
+	R: FnMut(&Route<'_, T>) -> K,
+	K: Ord + Clone,
+	S: FnMut(f32, f32) -> f32,
+{
+	let halt = advance_by(
+		state,
+		is_goal,
+		neighbours,
+		weight,
+		epsilon,
+		interrupt,
+		stats,
+		listener,
+		rank,
+		frontier_cap,
+		max_distance,
+		score,
+	)?;
+	Ok(match halt {
+		Halt::Goal(node, _) => PathResult::Found(state.path_to(node)),
+		Halt::Ended(result) => result,
+	})
+}
+
+/// Continues the search recorded in `state` as per [resume_by] without building the path
+/// to a goal found, for callers which only need part of it or none at all
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn advance_by<T, G, N, NI, W, I, L, R, K, S>(
+	state: &mut SearchState<T, K>,
+	mut is_goal: G,
+	mut neighbours: N,
+	mut weight: W,
+	epsilon: f32,
+	mut interrupt: I,
+	stats: &mut SearchStats,
+	listener: &mut L,
+	mut rank: R,
+	frontier_cap: usize,
+	max_distance: f32,
+	mut score: S,
+) -> Result<Halt<T>, AstarError<T>>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	G: FnMut(&T, &Route<'_, T>, f32) -> bool,
+	N: FnMut(&T) -> Result<NI, AstarError<T>>,
+	NI: IntoIterator<Item = (T, f32)>,
+	W: FnMut(&T) -> Result<(f32, f32), AstarError<T>>,
+	I: FnMut(usize) -> Option<PathResult<T>>,
+	L: SearchListener<T>,
+	R: FnMut(&Route<'_, T>) -> K,
+	K: Ord + Clone,
+	S: FnMut(f32, f32) -> f32,
+{
+	let SearchState {
+		node_astar_scores,
+		queue,
+		expanded,
+		predecessors,
+		start_node,
+		tie_break,
+	} = state;
+	let start_node = *start_node;
+	// If a path exists then the end node will shift to the head of the queue and we can return it.
+	// If a path does not exist the `queue` will empty and we return `NoPath` through a check
+	// at the start of each loop iteration, before the head of the queue is inspected.
+	loop {
+		match queue.peek() {
+			// As the `queue` is processed elements are removed, neighbours discovered and scores calculated.
+			// If the `queue` becomes empty then it means there are no routes to the end node
+			None => return Ok(Halt::Ended(PathResult::NoPath)),
+			// The goal at the head of the queue scores no worse than every other route still
+			// waiting, so no route left in the queue can reach it more cheaply and the answer
+			// is final. Stop here rather than draining the rest of the queue
+			Some(head) => {
+				let route = Route {
+					node: head.node,
+					from: head.from,
+					start_node,
+					predecessors,
+				};
+				if is_goal(&head.node, &route, head.distance) {
+					if let Some(from) = head.from {
+						predecessors.insert(head.node, from);
+					}
+					return Ok(Halt::Goal(head.node, head.distance));
+				}
+			}
+		}
+		if let Some(result) = interrupt(stats.expansions) {
+			return Ok(Halt::Ended(result));
+		}
+		// Remove the head ready for processing, it exists as it was just inspected
+		let current_path = match queue.pop() {
+			Some(x) => x,
+			None => return Ok(Halt::Ended(PathResult::NoPath)),
+		};
+		expanded.insert(current_path.node);
+		// Record where the node was reached from, every route extended from here walks back
+		// through it
+		if let Some(from) = current_path.from {
+			predecessors.insert(current_path.node, from);
+		}
+		listener.on_expand(
+			&current_path.node,
+			current_path.score,
+			current_path.distance,
+		);
+		// Process each new path to the neighbours of the current node
+		for n in neighbours(&current_path.node)? {
+			let distance_traveled_so_far: f32 = current_path.distance;
+			let distance_to_this_neighbour: f32 = n.1;
+			if distance_to_this_neighbour < 0.0 {
+				return Err(AstarError::NegativeDistance(n.0));
+			}
+			// Calculate the total distance from the start to this neighbour node
+			let distance_traveled = distance_traveled_so_far + distance_to_this_neighbour;
+			// Any extension of a route which is already too long is also too long
+			if distance_traveled > max_distance {
+				continue;
+			}
+			let (node_weight, stored_weight) = weight(&n.0)?;
 			// Now we know the overall distance traveled and the weight of where we're going to we can score it
-			let astar_score = a_star_score(distance_traveled, node_weight);
-			// Create a vector of the nodes traversed to get to this `n`
-			let mut previous_nodes_traversed = current_path.2.clone();
-			previous_nodes_traversed.push(current_path.0);
+			let astar_score = score(distance_traveled, node_weight);
+			// Reject anything that can't be ranked so that ordering the queue is always possible
+			if astar_score.is_nan() || stored_weight.is_nan() {
+				return Err(AstarError::NanRoute {
+					from: current_path.node,
+					to: n.0,
+					from_score: current_path.score,
+					score: astar_score,
+				});
+			}
+			let route_rank = rank(&Route {
+				node: n.0,
+				from: Some(current_path.node),
+				start_node,
+				predecessors,
+			});
 			// Update the a-star data set.
 			// If it already has a record of this node we choose to either update it or ignore this new path as it is worse than what we have calculated in a previous iteration
-			if node_astar_scores.contains_key(&n.0) {
-				if node_astar_scores.get(&n.0) >= Some(&astar_score) {
-					// `node_astar_scores` contains a worse score so update the map with the better score
-					node_astar_scores.insert(n.0, astar_score);
-					// Search the queue to see if we already have a route to this node.
-					// If we do but this new path is better then replace it, otherwise discard
-					let mut new_queue_item_required_for_node = true;
-					for q in queue.iter_mut() {
-						if q.0 == n.0 {
-							// If existing score is worse (higher) then replace the queue item and
-							// don't allow a fresh queue item to be added
-							if q.1 >= astar_score {
-								new_queue_item_required_for_node = false;
-								q.1 = astar_score;
-								q.2 = previous_nodes_traversed.clone();
-								q.3 = distance_traveled;
-							}
-						}
-					}
-					// Queue doesn't contain a route to this node, as we have now found a better route
-					// update the queue with it so it can be explored
-					if new_queue_item_required_for_node {
-						queue.push((
-							n.0,
-							astar_score,
-							previous_nodes_traversed,
-							distance_traveled,
-						));
-					}
+			let improved = match node_astar_scores.get(&n.0) {
+				Some((existing_score, _)) if expanded.contains(&n.0) => {
+					!no_worse_than(*existing_score, astar_score, epsilon)
 				}
-			} else {
+				// A route which ties with the one queued only replaces it if it ranks no worse
+				Some((existing_score, _))
+					if no_worse_than(*existing_score, astar_score, epsilon) =>
+				{
+					no_worse_than(astar_score, *existing_score, epsilon)
+						&& queue
+							.rank_of(&n.0)
+							.map_or(true, |queued_rank| route_rank <= *queued_rank)
+				}
+				Some((existing_score, _)) => no_worse_than(astar_score, *existing_score, epsilon),
 				// No record of node therefore this is the first time it has been visted
-				// Update the a-star score data
-				node_astar_scores.insert(n.0, astar_score);
-				// Update the queue with this new route to process later
-				queue.push((
-					n.0,
-					astar_score,
-					previous_nodes_traversed,
-					distance_traveled,
-				));
+				None => true,
+			};
+			if improved {
+				listener.on_relax(&current_path.node, &n.0, astar_score);
+				node_astar_scores.insert(n.0, (astar_score, distance_traveled));
+				// Queue the route to process later, replacing any route already queued for `n`
+				queue.push(QueueEntry {
+					node: n.0,
+					score: astar_score,
+					from: Some(current_path.node),
+					distance: distance_traveled,
+					lead: match tie_break {
+						TieBreak::DistanceTraveled => distance_traveled,
+						TieBreak::StoredWeight => 0.0,
+					},
+					stored_weight,
+					rank: route_rank,
+					id: 0,
+				});
 			}
 		}
+		queue.truncate(frontier_cap);
+		stats.expansions += 1;
+		stats.max_frontier = stats.max_frontier.max(queue.len());
+	}
+}
+
+/// A route to a node awaiting processing in the queue of [search]
+#[derive(Debug, Clone)]
+struct QueueEntry<T, K> {
+	/// The node this route reaches
+	node: T,
+	/// A-Star score of the route
+	score: f32,
+	/// The node this route was discovered from, `None` for the start node
+	from: Option<T>,
+	/// Total distance traveled along the route
+	distance: f32,
+	/// The distance compared between routes which tie on score, the distance traveled under
+	/// [TieBreak::DistanceTraveled] and `0.0` under [TieBreak::StoredWeight]
+	lead: f32,
+	/// The weight of `node` as stored in the graph, prior to any query time adjustment
+	stored_weight: f32,
+	/// Orders routes which tie on all of the above, see [search_by]
+	rank: K,
+	/// Identifies the order in which routes were queued, assigned by [Frontier::push]
+	id: usize,
+}
+
+impl<T, K: Ord> QueueEntry<T, K> {
+	/// Orders queue entries so the best route to process comes first:
+	/// 1. Lowest A-Star score
+	/// 2. Then longest distance traveled, as less of the score is made up of weight, unless
+	///    the search was made with [TieBreak::StoredWeight], see [TieBreak]
+	/// 3. Then lowest stored weight of the node reached, preferring routes which hug easy terrain
+	///
+	/// Entries which tie on all three are ordered by their `rank` and then processed in the
+	/// order they were queued
+	///
+	/// `NaN` values are rejected before routes are queued, should one slip through it is
+	/// treated as a tie
+	fn tie_break(a: &Self, b: &Self) -> Ordering {
+		let compare = |x: f32, y: f32| x.partial_cmp(&y).unwrap_or(Ordering::Equal);
+		compare(a.score, b.score)
+			.then_with(|| compare(b.lead, a.lead))
+			.then_with(|| compare(a.stored_weight, b.stored_weight))
+			.then_with(|| a.rank.cmp(&b.rank))
+			.then_with(|| a.id.cmp(&b.id))
+	}
+}
+
+impl<T, K: Ord> PartialEq for QueueEntry<T, K> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl<T, K: Ord> Eq for QueueEntry<T, K> {}
+
+impl<T, K: Ord> PartialOrd for QueueEntry<T, K> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<T, K: Ord> Ord for QueueEntry<T, K> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// reversed so the max-heap yields the best route, see [QueueEntry::tie_break]
+		QueueEntry::tie_break(other, self)
+	}
+}
 
-		// Sort the queue by a-star sores so each loop processes the current best path
-		queue.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+/// Queue of routes awaiting processing in [search], holding at most one live route per node.
+///
+/// Replacing the route to a node pushes the new route onto the heap and records its `id`
+/// in the index, the superseded entry is left in place and discarded once it reaches the
+/// head of the heap rather than being searched for
+#[derive(Debug, Clone)]
+struct Frontier<T, K> {
+	/// Routes ordered best first, including superseded entries yet to be discarded
+	heap: BinaryHeap<QueueEntry<T, K>>,
+	/// The `id` and `rank` of the live route to each node in `heap`
+	index: HashMap<T, (usize, K)>,
+	/// The `id` handed to the next route pushed
+	next_id: usize,
+}
 
-		// As the `queue` is processed elements are removed, neighbours discovered and scores calculated.
-		//If the `queue` length becomes zero then it means there are no routes to the `end_node` and we return `None`
-		if queue.is_empty() {
-			return None;
+impl<T, K> Frontier<T, K>
+where
+	T: Eq + Hash + Copy,
+	K: Ord + Clone,
+{
+	/// An empty queue
+	fn new() -> Self {
+		Frontier {
+			heap: BinaryHeap::new(),
+			index: HashMap::new(),
+			next_id: 0,
+		}
+	}
+	/// Number of live routes awaiting processing
+	fn len(&self) -> usize {
+		self.index.len()
+	}
+	/// Queues `entry`, superseding any route already queued to the same node
+	fn push(&mut self, mut entry: QueueEntry<T, K>) {
+		entry.id = self.next_id;
+		self.next_id += 1;
+		self.index
+			.insert(entry.node, (entry.id, entry.rank.clone()));
+		self.heap.push(entry);
+	}
+	/// Discards superseded entries from the head of the heap
+	fn discard_stale(&mut self) {
+		while let Some(head) = self.heap.peek() {
+			if self.index.get(&head.node).map(|live| live.0) == Some(head.id) {
+				break;
+			}
+			self.heap.pop();
+		}
+	}
+	/// The best live route without removing it
+	fn peek(&mut self) -> Option<&QueueEntry<T, K>> {
+		self.discard_stale();
+		self.heap.peek()
+	}
+	/// The `rank` of the live route to `node`, if one is queued
+	fn rank_of(&self, node: &T) -> Option<&K> {
+		self.index.get(node).map(|live| &live.1)
+	}
+	/// Drops all but the best `cap` live routes
+	fn truncate(&mut self, cap: usize) {
+		if self.len() <= cap {
+			return;
 		}
+		let index = &self.index;
+		let mut live: Vec<QueueEntry<T, K>> = self
+			.heap
+			.drain()
+			.filter(|entry| index.get(&entry.node).map(|live| live.0) == Some(entry.id))
+			.collect();
+		live.sort_by(QueueEntry::tie_break);
+		live.truncate(cap);
+		self.index = live
+			.iter()
+			.map(|entry| (entry.node, (entry.id, entry.rank.clone())))
+			.collect();
+		self.heap = live.into_iter().collect();
+	}
+	/// Replaces the score of every live route with the `score` of the node it reaches,
+	/// discarding superseded entries as the heap is rebuilt in the new order
+	fn rescore<S>(&mut self, mut score: S)
+	where
+		S: FnMut(&T) -> f32,
+	{
+		let index = &self.index;
+		let live: Vec<QueueEntry<T, K>> = self
+			.heap
+			.drain()
+			.filter(|entry| index.get(&entry.node).map(|live| live.0) == Some(entry.id))
+			.map(|mut entry| {
+				entry.score = score(&entry.node);
+				entry
+			})
+			.collect();
+		self.heap = live.into_iter().collect();
 	}
-	let mut best_path = queue[0].2.clone();
-	// add end node to data
-	best_path.push(end_node);
-	Some(best_path)
+	/// Removes and returns the best live route
+	fn pop(&mut self) -> Option<QueueEntry<T, K>> {
+		self.discard_stale();
+		let head = self.heap.pop()?;
+		self.index.remove(&head.node);
+		Some(head)
+	}
+}
+
+/// Whether `new_score` is better than or equal to `existing_score`, treating scores
+/// within `epsilon` of each other as equal so that floating point noise from summing
+/// distances doesn't decide between logically identical routes. For large scores the
+/// tolerance grows to [RELATIVE_SCORE_EPSILON] of `existing_score`, while an `epsilon` of
+/// `0.0` compares exactly
+pub(crate) fn no_worse_than(new_score: f32, existing_score: f32, epsilon: f32) -> bool {
+	let tolerance = if epsilon > 0.0 {
+		epsilon.max(existing_score.abs() * RELATIVE_SCORE_EPSILON)
+	} else {
+		0.0
+	};
+	new_score <= existing_score + tolerance
 }
 
 /// Determines a score to rank a chosen path, lower scores are better
-fn a_star_score(distance: f32, weighting: f32) -> f32 {
+pub(crate) fn a_star_score(distance: f32, weighting: f32) -> f32 {
 	distance + weighting
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::fixtures::{grid_nodes, hexagon_nodes, large_grid_nodes, no_path_nodes};
 	use std::collections::HashMap;
 
 	#[test]
@@ -230,6 +3113,18 @@ mod tests {
 		let _path = astar_path(start, &nodes, end);
 	}
 	#[test]
+	/// The missing start node is reported as an `Err` naming it rather than a panic
+	#[allow(clippy::type_complexity)]
+	fn safe_missing_start_node() {
+		let mut nodes: HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> = HashMap::new();
+		nodes.insert((0, 1), (vec![((0, 3), 4.0)], 4.0));
+		let result = astar_path_safe((0, 0), &nodes, (0, 1));
+		assert_eq!(
+			Err("Node data does not contain start node (0, 0)".to_string()),
+			result
+		);
+	}
+	#[test]
 	#[should_panic]
 	/// Expect a panic if the `nodes` data set doesn't contain the end node
 	#[allow(clippy::type_complexity)]
@@ -656,4 +3551,912 @@ mod tests {
 		let actual = vec![(3, 3), (3, 2), (3, 1), (2, 1), (1, 0), (0, 0)];
 		assert_eq!(actual, path);
 	}
+	#[test]
+	/// A range wide enough to contain every weight reproduces [astar_path]
+	fn clamped_wide_range_matches_unclamped() {
+		let nodes = grid_nodes();
+		let path = astar_path_clamped(0, &nodes, 15, 0.0, 100.0).unwrap();
+		assert_eq!(astar_path(0, &nodes, 15).unwrap(), path);
+	}
+	#[test]
+	/// Clamping the maximum weight flattens the heavy squares of the grid so the route changes
+	fn clamped_max_weight_changes_path() {
+		let nodes = grid_nodes();
+		let path = astar_path_clamped(0, &nodes, 15, 0.0, 1.0).unwrap();
+		assert_ne!(astar_path(0, &nodes, 15).unwrap(), path);
+		assert_eq!(vec![0, 4, 8, 12, 13, 14, 15], path);
+	}
+	#[test]
+	/// The next step on the grid is the second node of the full path
+	fn next_step_matches_path() {
+		let nodes = grid_nodes();
+		let path = astar_path(0, &nodes, 15).unwrap();
+		assert_eq!(Some(path[1]), next_step(0, &nodes, 15));
+	}
+	#[test]
+	/// Asking for the next step when already at the end returns the start
+	fn next_step_at_end() {
+		let nodes = grid_nodes();
+		assert_eq!(Some(15), next_step(15, &nodes, 15));
+	}
+	#[test]
+	/// A search with no time to spare on a large graph is abandoned
+	fn timed_out_on_large_graph() {
+		let nodes = large_grid_nodes(60);
+		let result = astar_path_timed_with_interval(0, &nodes, 3599, Duration::ZERO, 1);
+		assert_eq!(PathResult::Timeout, result);
+	}
+	#[test]
+	/// A generous time limit gives the same path as [astar_path]
+	fn timed_generous_finds_path() {
+		let nodes = grid_nodes();
+		let result = astar_path_timed(0, &nodes, 15, Duration::from_secs(60));
+		assert_eq!(PathResult::Found(vec![0, 4, 8, 9, 10, 11, 15]), result);
+	}
+	/// Builds a diamond where node `0` can reach `3` through `1` or `2`, the two routes
+	/// being of logically equal length but each leg using the supplied distances
+	fn diamond_nodes(via_1: (f32, f32), via_2: (f32, f32)) -> HashMap<i32, (Vec<(i32, f32)>, f32)> {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, via_1.0), (2, via_2.0)], 0.0));
+		nodes.insert(1, (vec![(3, via_1.1)], 0.0));
+		nodes.insert(2, (vec![(3, via_2.1)], 0.0));
+		nodes.insert(3, (vec![], 0.0));
+		nodes
+	}
+	#[test]
+	/// Both diamonds have routes of equal length, `0.9` and `1.4`, but in `f32` the rounding
+	/// error of summing the legs favours a different route in each. Comparing exactly lets
+	/// the noise decide whereas the default epsilon consistently treats them as a tie
+	fn epsilon_stabilises_float_ties() {
+		let rounds_up_via_1 = diamond_nodes((0.1, 0.8), (0.2, 0.7));
+		let rounds_down_via_1 = diamond_nodes((0.1, 1.3), (0.3, 1.1));
+		// exact comparisons flip-flop depending on the direction of the rounding error
+		assert_eq!(
+			Some(vec![0, 2, 3]),
+			astar_path_with_epsilon(0, &rounds_up_via_1, 3, 0.0)
+		);
+		assert_eq!(
+			Some(vec![0, 1, 3]),
+			astar_path_with_epsilon(0, &rounds_down_via_1, 3, 0.0)
+		);
+		// within tolerance the later discovered route wins the tie in both cases
+		assert_eq!(Some(vec![0, 2, 3]), astar_path(0, &rounds_up_via_1, 3));
+		assert_eq!(Some(vec![0, 2, 3]), astar_path(0, &rounds_down_via_1, 3));
+	}
+	#[test]
+	/// Finds a path along an infinite line of integers which is never materialised
+	fn lazy_infinite_line() {
+		let end = 5;
+		let result = astar_path_lazy(
+			-3,
+			end,
+			|n: &i32| vec![(n - 1, 1.0), (n + 1, 1.0)],
+			|n: &i32| (end - n).abs() as f32,
+			1000,
+		);
+		assert_eq!(PathResult::Found((-3..=5).collect::<Vec<i32>>()), result);
+	}
+	#[test]
+	/// A one-way infinite line can never reach a goal behind the start so the cap ends the search
+	fn lazy_expansion_cap() {
+		let result = astar_path_lazy(0, -1, |n: &i32| vec![(n + 1, 1.0)], |_: &i32| 0.0, 50);
+		assert_eq!(PathResult::ExpansionLimit, result);
+	}
+	#[test]
+	/// The start's only neighbour is a dead-end while the end node sits elsewhere, once both
+	/// have been expanded the queue is empty and the search must return `None` rather than
+	/// inspect the head of an empty queue
+	fn dead_end_empties_queue() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 1.0));
+		nodes.insert(1, (vec![], 1.0));
+		nodes.insert(2, (vec![(0, 1.0)], 1.0));
+		assert_eq!(None, astar_path(0, &nodes, 2));
+		// a start without any neighbours empties the queue on the very first expansion
+		assert_eq!(None, astar_path(1, &nodes, 2));
+	}
+	#[test]
+	/// The grid path can be collected straight into a `VecDeque`
+	fn collect_into_vec_deque() {
+		let nodes = grid_nodes();
+		let mut path: std::collections::VecDeque<i32> = astar_path_collect(0, &nodes, 15).unwrap();
+		assert_eq!(Some(0), path.pop_front());
+		assert_eq!(Some(15), path.pop_back());
+	}
+	#[test]
+	/// Overriding node `9` with a large weight forces a detour while leaving the grid untouched
+	fn override_weight_detours() {
+		let nodes = grid_nodes();
+		let original = nodes.clone();
+		let mut overrides = HashMap::new();
+		overrides.insert(9, 100.0);
+		let path = astar_path_override(0, &nodes, 15, &overrides).unwrap();
+		assert_eq!(vec![0, 1, 2, 3, 7, 11, 15], path);
+		assert_eq!(original, nodes);
+		assert_eq!(
+			vec![0, 4, 8, 9, 10, 11, 15],
+			astar_path(0, &nodes, 15).unwrap()
+		);
+	}
+	#[test]
+	/// The reversed path mirrors the forward path through the hexagon grid
+	fn reverse_mirrors_hexagon_path() {
+		let nodes = hexagon_nodes();
+		let (forward, reverse) = astar_path_with_reverse((0, 0), &nodes, (3, 3)).unwrap();
+		assert_eq!(
+			vec![(0, 0), (1, 0), (2, 1), (3, 1), (3, 2), (3, 3)],
+			forward
+		);
+		assert_eq!(forward.len(), reverse.len());
+		for (i, node) in forward.iter().enumerate() {
+			assert_eq!(*node, reverse[reverse.len() - 1 - i]);
+		}
+	}
+	#[test]
+	/// Clamping makes nodes `1` and `2` score identically at the same distance, the route
+	/// through `2` wins as its stored weight is lower despite `1` being discovered first
+	fn tie_break_prefers_low_stored_weight() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 0.0));
+		nodes.insert(1, (vec![(3, 1.0)], 50.0));
+		nodes.insert(2, (vec![(3, 1.0)], 5.0));
+		nodes.insert(3, (vec![], 0.0));
+		let path = astar_path_clamped(0, &nodes, 3, 0.0, 3.0).unwrap();
+		assert_eq!(vec![0, 2, 3], path);
+	}
+	#[test]
+	/// The direct route to `5` passes `1` which neighbours a guard at `4`, with enough of a
+	/// penalty the longer route through `2` and `3` is taken to keep away from the guard
+	fn avoid_proximity_to_danger() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 0.0));
+		nodes.insert(1, (vec![(5, 1.0), (4, 1.0)], 0.0));
+		nodes.insert(2, (vec![(3, 1.0)], 0.0));
+		nodes.insert(3, (vec![(5, 1.0)], 0.0));
+		nodes.insert(4, (vec![(1, 1.0)], 0.0));
+		nodes.insert(5, (vec![], 0.0));
+		assert_eq!(vec![0, 1, 5], astar_path(0, &nodes, 5).unwrap());
+		let path = astar_path_avoid_proximity(0, &nodes, 5, &[4], 20.0).unwrap();
+		assert_eq!(vec![0, 2, 3, 5], path);
+		// without any dangers the result is unchanged
+		let path = astar_path_avoid_proximity(0, &nodes, 5, &[], 20.0).unwrap();
+		assert_eq!(vec![0, 1, 5], path);
+	}
+	#[test]
+	/// Missing start and end nodes are reported as errors rather than panics
+	fn try_missing_start_and_end() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 1.0));
+		nodes.insert(1, (vec![], 1.0));
+		assert_eq!(
+			Err(AstarError::MissingStartNode(7)),
+			try_astar_path(7, &nodes, 1)
+		);
+		assert_eq!(
+			Err(AstarError::MissingEndNode(7)),
+			try_astar_path(0, &nodes, 7)
+		);
+	}
+	#[test]
+	/// A neighbour which isn't a key of the data set is reported when it is discovered
+	fn try_missing_neighbour() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (5, 1.0)], 1.0));
+		nodes.insert(1, (vec![(2, 1.0)], 1.0));
+		nodes.insert(2, (vec![], 1.0));
+		assert_eq!(
+			Err(AstarError::MissingNode(5)),
+			try_astar_path(0, &nodes, 2)
+		);
+	}
+	#[test]
+	/// A goal which is only referenced as the neighbour of another node is reported up front
+	/// as missing, rather than once the search discovers it, and the same sink part way along
+	/// a route is reported as a missing node
+	fn try_missing_sink() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 1.0));
+		nodes.insert(1, (vec![(2, 1.0), (3, 1.0)], 1.0));
+		nodes.insert(3, (vec![], 1.0));
+		assert_eq!(
+			Err(AstarError::MissingEndNode(2)),
+			try_astar_path(0, &nodes, 2)
+		);
+		nodes.insert(3, (vec![(2, 1.0), (4, 5.0)], 1.0));
+		nodes.insert(4, (vec![], 1.0));
+		assert_eq!(
+			Err(AstarError::MissingNode(2)),
+			try_astar_path(0, &nodes, 4)
+		);
+	}
+	#[test]
+	/// `NaN` distances, weights and scores are reported instead of panicking while sorting
+	fn try_nan_scores() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, f32::NAN), (2, 1.0)], 1.0));
+		nodes.insert(1, (vec![(3, 1.0)], 1.0));
+		nodes.insert(2, (vec![(3, 1.0)], 1.0));
+		nodes.insert(3, (vec![], 1.0));
+		assert_nan_route(try_astar_path(0, &nodes, 3), 0, 1);
+
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 1.0));
+		nodes.insert(1, (vec![], f32::NAN));
+		assert_nan_route(try_astar_path(0, &nodes, 1), 0, 1);
+		assert_eq!(Err(AstarError::NanScore(1)), try_astar_path(1, &nodes, 0));
+
+		// infinite distances and weights of opposite sign sum to NaN
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, f32::INFINITY)], 1.0));
+		nodes.insert(1, (vec![], f32::NEG_INFINITY));
+		assert_nan_route(try_astar_path(0, &nodes, 1), 0, 1);
+	}
+	/// Asserts that `result` failed on a `NaN` route from `from` to `to`
+	fn assert_nan_route(result: Result<Option<Vec<i32>>, AstarError<i32>>, from: i32, to: i32) {
+		match result {
+			Err(AstarError::NanRoute {
+				from: f,
+				to: t,
+				score,
+				..
+			}) => {
+				assert_eq!((from, to), (f, t));
+				assert!(score.is_nan());
+			}
+			other => panic!("Expected a NaN route, found {:?}", other),
+		}
+	}
+	#[test]
+	/// A `NaN` weight deep in the grid is pinned to the edge which reached it, along with
+	/// the score of the route it extended
+	fn nan_route_names_both_nodes() {
+		let mut nodes = grid_nodes();
+		nodes.get_mut(&10).unwrap().1 = f32::NAN;
+		let error = try_astar_path(0, &nodes, 15).unwrap_err();
+		match error {
+			AstarError::NanRoute {
+				from,
+				to,
+				from_score,
+				score,
+			} => {
+				assert_eq!(10, to);
+				assert!(from == 9 || from == 6 || from == 11 || from == 14);
+				assert!(from_score.is_finite());
+				assert!(score.is_nan());
+			}
+			other => panic!("Expected a NaN route, found {:?}", other),
+		}
+		assert!(error.to_string().contains("node 10"));
+	}
+	#[test]
+	/// Well formed data behaves just like [astar_path]
+	fn try_matches_astar_path() {
+		let nodes = grid_nodes();
+		assert_eq!(Ok(astar_path(0, &nodes, 15)), try_astar_path(0, &nodes, 15));
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![], 1.0));
+		nodes.insert(1, (vec![], 1.0));
+		assert_eq!(Ok(None), try_astar_path(0, &nodes, 1));
+	}
+	#[test]
+	#[should_panic(expected = "is not a key in the `nodes` data set")]
+	/// [astar_path] still panics on malformed data, with the error message
+	fn missing_neighbour_panics() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(5, 1.0)], 1.0));
+		nodes.insert(1, (vec![], 1.0));
+		let _path = astar_path(0, &nodes, 1);
+	}
+	#[test]
+	/// The report of the README example separates the distance of `5 + 20` from the weights
+	/// of `1 + 1 + 2` along `S -> O2 -> E`
+	#[allow(clippy::type_complexity)]
+	fn report_readme_example() {
+		let mut nodes: HashMap<(i32, i32), (Vec<((i32, i32), f32)>, f32)> = HashMap::new();
+		nodes.insert((0, 0), (vec![((0, 1), 22.0), ((0, 2), 5.0)], 1.0)); // S
+		nodes.insert((0, 1), (vec![((0, 3), 4.0)], 4.0)); // O1
+		nodes.insert((0, 2), (vec![((0, 3), 20.0)], 1.0)); // O2
+		nodes.insert((0, 3), (vec![], 2.0)); // E
+		let report = astar_path_report((0, 0), &nodes, (0, 3)).unwrap();
+		assert_eq!(vec![(0, 0), (0, 2), (0, 3)], report.nodes);
+		assert_eq!(25.0, report.total_distance);
+		assert_eq!(4.0, report.total_weight);
+		assert_eq!(27.0, report.final_score);
+	}
+	#[test]
+	/// The path finishes at the end node without repeating it, even when the end node has
+	/// an edge to itself and back towards the start
+	fn end_node_appears_once() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 1.0));
+		nodes.insert(1, (vec![(2, 1.0)], 1.0));
+		nodes.insert(2, (vec![(2, 0.0), (0, 1.0)], 1.0));
+		let path = astar_path(0, &nodes, 2).unwrap();
+		assert_eq!(vec![0, 1, 2], path);
+		assert_eq!(1, path.iter().filter(|node| **node == 2).count());
+		let grid_path = astar_path(0, &grid_nodes(), 15).unwrap();
+		assert_eq!(1, grid_path.iter().filter(|node| **node == 15).count());
+	}
+	#[test]
+	/// A grid whose adjacency includes diagonals measured by Euclidean distance cuts
+	/// straight across the corner rather than following the edges
+	fn topo_euclidean_grid() {
+		let mut adjacency: HashMap<(i32, i32), Vec<(i32, i32)>> = HashMap::new();
+		let mut weights: HashMap<(i32, i32), f32> = HashMap::new();
+		for x in 0..3 {
+			for y in 0..3 {
+				let mut neighbours = Vec::new();
+				for dx in -1..=1 {
+					for dy in -1..=1 {
+						let (nx, ny) = (x + dx, y + dy);
+						if (dx, dy) != (0, 0) && (0..3).contains(&nx) && (0..3).contains(&ny) {
+							neighbours.push((nx, ny));
+						}
+					}
+				}
+				adjacency.insert((x, y), neighbours);
+				weights.insert((x, y), 1.0);
+			}
+		}
+		let euclidean = |a: &(i32, i32), b: &(i32, i32)| {
+			(((a.0 - b.0).pow(2) + (a.1 - b.1).pow(2)) as f32).sqrt()
+		};
+		let path = astar_path_topo((0, 0), &adjacency, &weights, (2, 2), euclidean).unwrap();
+		assert_eq!(vec![(0, 0), (1, 1), (2, 2)], path);
+	}
+	#[test]
+	/// A start node with an impassable weight doesn't prevent a route being planned off it
+	fn start_with_infinite_weight() {
+		let mut nodes = grid_nodes();
+		nodes.get_mut(&0).unwrap().1 = f32::INFINITY;
+		let path = astar_path(0, &nodes, 15).unwrap();
+		assert_eq!(vec![0, 4, 8, 9, 10, 11, 15], path);
+		nodes.get_mut(&0).unwrap().1 = 1.0e9;
+		let path = astar_path(0, &nodes, 15).unwrap();
+		assert_eq!(vec![0, 4, 8, 9, 10, 11, 15], path);
+	}
+	#[test]
+	/// Fuzzing regression, a zero distance self-loop and a zero distance cycle between two
+	/// nodes tie with the routes which discovered them and were expanded without end
+	fn zero_distance_cycles_terminate() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 0.0));
+		nodes.insert(1, (vec![(1, 0.0), (2, 0.0), (3, 1.0)], 0.0));
+		nodes.insert(2, (vec![(1, 0.0)], 0.0));
+		nodes.insert(3, (vec![], 0.0));
+		assert_eq!(Ok(Some(vec![0, 1, 3])), try_astar_path(0, &nodes, 3));
+	}
+	#[test]
+	/// Fuzzing regression, a route of infinite score ties with itself around a self-loop
+	fn infinite_score_self_loop_terminates() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, f32::INFINITY)], 0.0));
+		nodes.insert(1, (vec![(1, 0.0), (2, 1.0)], 0.0));
+		nodes.insert(2, (vec![], 0.0));
+		assert_eq!(Ok(Some(vec![0, 1, 2])), try_astar_path(0, &nodes, 2));
+	}
+	#[test]
+	/// Fuzzing regression, a negative self-loop shrank the distance traveled on every lap
+	fn negative_distance_is_an_error() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 2.0)], 1.0));
+		nodes.insert(1, (vec![(1, -4.0), (2, 1.0)], 3.0));
+		nodes.insert(2, (vec![], 4.0));
+		assert_eq!(
+			Err(AstarError::NegativeDistance(1)),
+			try_astar_path(0, &nodes, 2)
+		);
+	}
+	#[test]
+	/// The frontier holds at most one route per node so it can never grow beyond the
+	/// number of distinct nodes, even where many routes to the same node are discovered
+	fn frontier_bounded_by_distinct_nodes() {
+		let nodes = large_grid_nodes(30);
+		let (path, stats) = astar_path_with_stats(0, &nodes, 899);
+		assert!(path.is_some());
+		assert!(stats.max_frontier > 0);
+		assert!(stats.max_frontier <= nodes.len());
+		assert!(stats.expansions <= nodes.len());
+	}
+	#[test]
+	/// The absence of a path is an error while a path found is `Ok`
+	fn result_no_path() {
+		assert_eq!(Err(NoPath), astar_path_result(0, &no_path_nodes(), 5));
+		assert_eq!(
+			Ok(vec![0, 4, 8, 9, 10, 11, 15]),
+			astar_path_result(0, &grid_nodes(), 15)
+		);
+	}
+	#[test]
+	/// On a grid of equal weights the ordered search gives the same path however the
+	/// neighbours of each node are arranged
+	fn ordered_ignores_neighbour_order() {
+		let mut nodes = grid_nodes();
+		for (neighbours, weight) in nodes.values_mut() {
+			neighbours.sort_by_key(|(n, _)| *n);
+			*weight = 1.0;
+		}
+		let expected = astar_path_ordered(0, &nodes, 15);
+		assert!(expected.is_some());
+		for rotation in 0..4 {
+			let mut shuffled = nodes.clone();
+			for (neighbours, _) in shuffled.values_mut() {
+				let len = neighbours.len();
+				neighbours.rotate_left(rotation % len);
+				if rotation % 2 == 1 {
+					neighbours.reverse();
+				}
+			}
+			assert_eq!(expected, astar_path_ordered(0, &shuffled, 15));
+		}
+	}
+	#[test]
+	/// On an open grid whose weights are the manhattan distance to the goal every monotone
+	/// route ties, with neighbours listed in varying orders the unbiased search zig-zags
+	/// while the turn bias gives a single turn at the same cost
+	fn turn_bias_straightens_open_grid() {
+		let width = 8;
+		let end = (5, 5);
+		let cell = |n: &usize| ((n % width) as i32, (n / width) as i32);
+		let weights: Vec<f32> = (0..64)
+			.map(|n| {
+				let (x, y) = cell(&n);
+				((x - end.0).abs() + (y - end.1).abs()) as f32
+			})
+			.collect();
+		let mut nodes = map_labels(&flat_grid_graph(&weights, width, Connectivity::Four), cell);
+		for ((x, y), (neighbours, _)) in nodes.iter_mut() {
+			let len = neighbours.len();
+			neighbours.rotate_left((x + y) as usize % len);
+		}
+		let turns = |path: &[(i32, i32)]| {
+			grid_path_directions(path)
+				.windows(2)
+				.filter(|pair| pair[0] != pair[1])
+				.count()
+		};
+		let straight = astar_path_grid((0, 0), &nodes, end).unwrap();
+		let unbiased = astar_path_grid_with_turn_bias((0, 0), &nodes, end, false).unwrap();
+		assert_eq!(1, turns(&straight));
+		assert!(turns(&unbiased) > 1);
+		assert_eq!(straight.len(), unbiased.len());
+		let cost = |path: &[(i32, i32)]| -> f32 {
+			path.windows(2)
+				.map(|pair| graph::edge_distance(&nodes, &pair[0], &pair[1]).unwrap())
+				.sum()
+		};
+		assert_eq!(cost(&straight), cost(&unbiased));
+	}
+	#[test]
+	/// Across the grid the cheapest path takes six hops, with only five allowed the direct
+	/// but costly shortcut from the corner has to be taken instead
+	fn hop_limit_forces_shorter_costlier_route() {
+		let mut nodes = grid_nodes();
+		assert_eq!(
+			Some(7),
+			astar_path_max_hops(0, &nodes, 15, 6).map(|p| p.len())
+		);
+		assert_eq!(
+			astar_path(0, &nodes, 15),
+			astar_path_max_hops(0, &nodes, 15, 100)
+		);
+		assert_eq!(None, astar_path_max_hops(0, &nodes, 15, 5));
+		nodes.get_mut(&0).unwrap().0.push((10, 30.0));
+		assert_eq!(
+			astar_path(0, &nodes, 15),
+			astar_path_max_hops(0, &nodes, 15, 6)
+		);
+		let path = astar_path_max_hops(0, &nodes, 15, 5).unwrap();
+		assert_eq!(vec![0, 10], path[..2].to_vec());
+		assert!(path.len() <= 6);
+		assert_eq!(Some(&15), path.last());
+	}
+	#[test]
+	/// A cheap route of a short hop then a long jump wins until the smoothness weight makes
+	/// its unevenness cost more than the detour of the route of even steps
+	fn smoothness_weight_prefers_even_steps() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		// jerky: steps of 1, 5 and 1 for a distance of 7
+		nodes.insert(0, (vec![(1, 1.0), (3, 2.5)], 0.0));
+		nodes.insert(1, (vec![(2, 5.0)], 0.0));
+		nodes.insert(2, (vec![(5, 1.0)], 0.0));
+		// even: three steps of 2.5 for a distance of 7.5
+		nodes.insert(3, (vec![(4, 2.5)], 0.0));
+		nodes.insert(4, (vec![(5, 2.5)], 0.0));
+		nodes.insert(5, (vec![], 0.0));
+		let jerky = vec![0, 1, 2, 5];
+		let even = vec![0, 3, 4, 5];
+		assert_eq!(Some(32.0), path_smoothness(&jerky, &nodes));
+		assert_eq!(Some(0.0), path_smoothness(&even, &nodes));
+		assert_eq!(Some(jerky.clone()), astar_path_smooth(0, &nodes, 5, 0.0));
+		assert_eq!(
+			astar_path(0, &nodes, 5),
+			astar_path_smooth(0, &nodes, 5, 0.0)
+		);
+		assert_eq!(Some(jerky), astar_path_smooth(0, &nodes, 5, 0.01));
+		assert_eq!(Some(even), astar_path_smooth(0, &nodes, 5, 0.1));
+	}
+	#[test]
+	/// A cached path is stale once the version is bumped, or once the checksum of the graph
+	/// changes after an edit, but not when the same graph is stored in a different order
+	fn cached_path_goes_stale() {
+		let mut nodes = grid_nodes();
+		let cached = astar_path_versioned(0, &nodes, 15, 1).unwrap();
+		assert_eq!(astar_path(0, &nodes, 15).as_deref(), Some(cached.path()));
+		assert!(!cached.is_stale(1));
+		assert!(cached.is_stale(2));
+		let checksum = graph_checksum(&nodes);
+		let mut entries: Vec<_> = nodes.clone().into_iter().collect();
+		entries.reverse();
+		let reordered: HashMap<i32, (Vec<(i32, f32)>, f32)> = entries.into_iter().collect();
+		assert_eq!(checksum, graph_checksum(&reordered));
+		let cached = astar_path_versioned(0, &nodes, 15, checksum).unwrap();
+		nodes.get_mut(&9).unwrap().0[0].1 = 2.0;
+		assert!(cached.is_stale(graph_checksum(&nodes)));
+	}
+	#[test]
+	/// Travelling down the hill from `1` to `0` is cheaper than climbing back up, so the
+	/// direct edge is taken one way and a detour the other
+	fn directional_costs_differ() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 2.0), (2, 2.0)], 0.0));
+		nodes.insert(1, (vec![(0, 2.0), (2, 2.0)], 0.0));
+		nodes.insert(2, (vec![(0, 2.0), (1, 2.0)], 0.0));
+		let mut multipliers = HashMap::new();
+		multipliers.insert((0, 1), 3.0);
+		multipliers.insert((1, 0), 0.5);
+		let cost = |path: &[i32]| -> f32 {
+			path.windows(2)
+				.map(|step| 2.0 * multipliers.get(&(step[0], step[1])).unwrap_or(&1.0))
+				.sum()
+		};
+		let down = astar_path_directional(1, &nodes, 0, &multipliers).unwrap();
+		let up = astar_path_directional(0, &nodes, 1, &multipliers).unwrap();
+		assert_eq!(vec![1, 0], down);
+		assert_eq!(vec![0, 2, 1], up);
+		assert_eq!(1.0, cost(&down));
+		assert_eq!(4.0, cost(&up));
+		// without multipliers both directions are the same direct edge
+		assert_eq!(
+			Some(vec![0, 1]),
+			astar_path_directional(0, &nodes, 1, &HashMap::new())
+		);
+	}
+	#[test]
+	/// The goal is reached directly from the start while fifty other routes wait in the
+	/// queue, the search stops as soon as the goal reaches the head rather than expanding them
+	fn stops_when_goal_dequeued() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		let mut neighbours: Vec<(i32, f32)> = (1..=50).map(|n| (n, 1.0)).collect();
+		neighbours.push((100, 1.0));
+		nodes.insert(0, (neighbours, 0.0));
+		for n in 1..=50 {
+			nodes.insert(n, (vec![(100, 1.0)], 10.0));
+		}
+		nodes.insert(100, (vec![], 0.0));
+		let (path, stats) = astar_path_with_stats(0, &nodes, 100);
+		assert_eq!(Some(vec![0, 100]), path);
+		assert_eq!(1, stats.expansions);
+		assert_eq!(51, stats.max_frontier);
+	}
+	#[test]
+	/// Along a line whose weights fall towards the goal the estimated progress never
+	/// decreases and finishes complete
+	fn progress_non_decreasing_on_line() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		for n in 0..10 {
+			let mut neighbours = vec![(n + 1, 1.0)];
+			if n > 0 {
+				neighbours.push((n - 1, 1.0));
+			}
+			nodes.insert(n, (neighbours, (10 - n) as f32));
+		}
+		nodes.insert(10, (vec![(9, 1.0)], 0.0));
+		let mut reported = Vec::new();
+		let mut listener = ProgressListener::new(0, &nodes, |fraction| reported.push(fraction));
+		let path = astar_path_with_listener(0, &nodes, 10, &mut listener);
+		assert_eq!(Some((0..=10).collect()), path);
+		assert!(reported.windows(2).all(|pair| pair[0] <= pair[1]));
+		assert!(reported
+			.iter()
+			.all(|fraction| (0.0..=1.0).contains(fraction)));
+		assert_eq!(Some(&0.0), reported.first());
+		assert_eq!(Some(&1.0), reported.last());
+	}
+	#[test]
+	/// A graph shared behind an `Arc` is queried from two threads at once without being
+	/// cloned or explicitly dereferenced
+	fn arc_shared_across_threads() {
+		let nodes = std::sync::Arc::new(grid_nodes());
+		let shared = std::sync::Arc::clone(&nodes);
+		let handle = std::thread::spawn(move || astar_path(15, &shared, 0));
+		assert_eq!(
+			Some(vec![0, 4, 8, 9, 10, 11, 15]),
+			astar_path(0, &nodes, 15)
+		);
+		let reversed = handle.join().unwrap().unwrap();
+		assert_eq!((Some(&15), Some(&0)), (reversed.first(), reversed.last()));
+	}
+	#[test]
+	/// Two parallel routes across a ladder give two edge-disjoint paths, after which no
+	/// route remains
+	fn edge_disjoint_parallel_routes() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (4, 1.0)], 0.0));
+		nodes.insert(1, (vec![(2, 1.0)], 0.0));
+		nodes.insert(2, (vec![(3, 1.0), (5, 0.5)], 0.0));
+		nodes.insert(3, (vec![(7, 1.0)], 0.0));
+		nodes.insert(4, (vec![(5, 1.5)], 0.0));
+		nodes.insert(5, (vec![(6, 1.5)], 0.0));
+		nodes.insert(6, (vec![(7, 1.5)], 0.0));
+		nodes.insert(7, (vec![], 0.0));
+		let paths = astar_edge_disjoint_paths(0, &nodes, 7, 5);
+		assert_eq!(vec![vec![0, 1, 2, 3, 7], vec![0, 4, 5, 6, 7]], paths);
+		let mut edges = HashSet::new();
+		for path in paths.iter() {
+			for step in path.windows(2) {
+				assert!(edges.insert((step[0], step[1])));
+			}
+		}
+		assert_eq!(
+			vec![vec![0, 1, 2, 3, 7]],
+			astar_edge_disjoint_paths(0, &nodes, 7, 1)
+		);
+		assert_eq!(vec![vec![3]], astar_edge_disjoint_paths(3, &nodes, 3, 2));
+	}
+	#[test]
+	/// Of two roads joining the same pair of nodes the shorter is taken and its label
+	/// reported, whichever order they are listed in
+	#[allow(clippy::type_complexity)]
+	fn edge_labels_parallel_roads() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32, &str)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 3.0, "toll road"), (1, 5.0, "back road")], 1.0));
+		nodes.insert(1, (vec![(2, 1.0, "high street")], 1.0));
+		nodes.insert(2, (vec![], 1.0));
+		let expected = vec![(0, None), (1, Some("toll road")), (2, Some("high street"))];
+		assert_eq!(Some(expected), astar_path_with_edge_labels(0, &nodes, 2));
+		nodes.insert(0, (vec![(1, 5.0, "toll road"), (1, 3.0, "back road")], 1.0));
+		let expected = vec![(0, None), (1, Some("back road")), (2, Some("high street"))];
+		assert_eq!(Some(expected), astar_path_with_edge_labels(0, &nodes, 2));
+		assert_eq!(
+			Some(vec![(1, None)]),
+			astar_path_with_edge_labels(1, &nodes, 1)
+		);
+	}
+	#[test]
+	/// Every hop across the grid costs `1.0` and there is one for each step of the path
+	fn grid_edge_costs() {
+		let nodes = grid_nodes();
+		let path = astar_path(0, &nodes, 15).unwrap();
+		let costs = astar_path_edge_costs(0, &nodes, 15).unwrap();
+		assert_eq!(path.len() - 1, costs.len());
+		assert!(costs.iter().all(|cost| *cost == 1.0));
+		assert_eq!(Some(vec![]), astar_path_edge_costs(5, &nodes, 5));
+	}
+	#[test]
+	/// Confined to the bottom row and right hand column the grid path follows the corridor
+	/// rather than its usual route up the left hand side
+	fn within_grid_corridor() {
+		let nodes = grid_nodes();
+		let corridor: HashSet<i32> = [0, 1, 2, 3, 7, 11, 15].iter().copied().collect();
+		assert_eq!(
+			Some(vec![0, 1, 2, 3, 7, 11, 15]),
+			astar_path_within(0, &nodes, 15, &corridor)
+		);
+		// the end lying outside of the corridor can't be reached
+		assert_eq!(None, astar_path_within(0, &nodes, 14, &corridor));
+		// nor can it be reached when the corridor is broken
+		let broken: HashSet<i32> = [0, 1, 3, 7, 11, 15].iter().copied().collect();
+		assert_eq!(None, astar_path_within(0, &nodes, 15, &broken));
+	}
+	#[test]
+	/// Two routes of equal length each cross one difficult node, `1` early on or `4` late.
+	/// Without fatigue the lighter node `4` is preferred, with fatigue its weight grows by
+	/// the time it is reached and the route through `1` is taken instead
+	fn fatigue_tackles_difficult_terrain_early() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (3, 1.0)], 0.0));
+		nodes.insert(1, (vec![(2, 1.0)], 5.0));
+		nodes.insert(2, (vec![(5, 1.0)], 0.0));
+		nodes.insert(3, (vec![(4, 1.0)], 0.0));
+		nodes.insert(4, (vec![(5, 1.0)], 3.5));
+		nodes.insert(5, (vec![], 0.0));
+		assert_eq!(
+			Some(vec![0, 3, 4, 5]),
+			astar_path_fatigue(0, &nodes, 5, 0.0)
+		);
+		assert_eq!(
+			astar_path(0, &nodes, 5),
+			astar_path_fatigue(0, &nodes, 5, 0.0)
+		);
+		assert_eq!(
+			Some(vec![0, 1, 2, 5]),
+			astar_path_fatigue(0, &nodes, 5, 0.5)
+		);
+	}
+	#[test]
+	/// Expanding a node of the grid queues routes to several of its neighbours, so more
+	/// routes are relaxed than nodes expanded
+	fn counting_listener_on_grid() {
+		#[derive(Default)]
+		/// Counts the notifications received
+		struct Counter {
+			/// Number of nodes expanded
+			expanded: usize,
+			/// Number of routes queued
+			relaxed: usize,
+			/// Whether the search has finished
+			finished: bool,
+		}
+		impl SearchListener<i32> for Counter {
+			fn on_expand(&mut self, _node: &i32, _score: f32, _distance: f32) {
+				self.expanded += 1;
+			}
+			fn on_relax(&mut self, _from: &i32, _to: &i32, _new_score: f32) {
+				self.relaxed += 1;
+			}
+			fn on_finish(&mut self, path: &Option<Vec<i32>>) {
+				self.finished = path.is_some();
+			}
+		}
+		let nodes = grid_nodes();
+		let mut counter = Counter::default();
+		let path = astar_path_with_listener(0, &nodes, 15, &mut counter);
+		assert_eq!(astar_path(0, &nodes, 15), path);
+		let (_, stats) = astar_path_with_stats(0, &nodes, 15);
+		assert_eq!(stats.expansions, counter.expanded);
+		assert!(counter.relaxed > counter.expanded);
+		assert!(counter.finished);
+	}
+	#[test]
+	/// Two logically equal routes reach node `3`, directly over `0 -> 1 -> 2 -> 3` and from
+	/// the heavily weighted `4` over a bundle of parallel edges. At this magnitude summing the
+	/// distances in a different order leaves the route from `4` a rounding error shorter, by
+	/// more than the absolute epsilon. The relative epsilon treats it as a tie so `3`, which
+	/// has already been expanded by then, isn't reopened and no node is expanded twice
+	fn parallel_edges_expand_each_node_once() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 10_000.1), (4, 10_000.1)], 0.0));
+		nodes.insert(1, (vec![(2, 0.7)], 0.0));
+		nodes.insert(2, (vec![(3, 0.4)], 0.0));
+		nodes.insert(3, (vec![(5, 1.0)], 0.0));
+		nodes.insert(4, (vec![(3, 1.1); 8], 5.0));
+		for n in 5..15 {
+			nodes.insert(n, (vec![(n + 1, 1.0)], 0.0));
+		}
+		nodes.insert(15, (vec![], 0.0));
+		/// Counts how many times each node is expanded
+		struct Expansions(HashMap<i32, usize>);
+		impl SearchListener<i32> for Expansions {
+			fn on_expand(&mut self, node: &i32, _score: f32, _distance: f32) {
+				*self.0.entry(*node).or_insert(0) += 1;
+			}
+		}
+		let mut expansions = Expansions(HashMap::new());
+		let path = astar_path_with_listener(0, &nodes, 15, &mut expansions);
+		let mut expected = vec![0, 1, 2, 3];
+		expected.extend(5..16);
+		assert_eq!(Some(expected), path);
+		assert!(expansions.0.values().all(|count| *count == 1));
+		assert_eq!(15, expansions.0.len());
+		// repeated searches are stable
+		let (first, stats) = astar_path_with_stats(0, &nodes, 15);
+		for _ in 0..3 {
+			assert_eq!((first.clone(), stats), astar_path_with_stats(0, &nodes, 15));
+		}
+		assert_eq!(path, first);
+		assert_eq!(15, stats.expansions);
+	}
+	#[test]
+	/// The cost of every grid path is available from its handle, whose path matches
+	/// [astar_path] when taken
+	fn handle_cost_and_path() {
+		let nodes = grid_nodes();
+		let costs: Vec<f32> = (0..16)
+			.map(|end| astar_handle(0, &nodes, end).unwrap().cost())
+			.collect();
+		assert_eq!(6.0, costs[15]);
+		assert_eq!(0.0, costs[0]);
+		let handle = astar_handle(0, &nodes, 15).unwrap();
+		assert_eq!(15, handle.goal());
+		assert_eq!(7, handle.len());
+		assert!(!handle.is_empty());
+		assert_eq!(astar_path(0, &nodes, 15), Some(handle.into_path()));
+		let handle = astar_handle(0, &nodes, 0).unwrap();
+		assert_eq!(1, handle.len());
+		assert_eq!(vec![0], handle.into_path());
+		assert_eq!(None, astar_handle(0, &no_path_nodes(), 5));
+	}
+	#[test]
+	/// The weights returned alongside the grid path are the stored weight of each node in
+	/// the order it's traveled
+	fn weights_align_with_grid_path() {
+		let nodes = grid_nodes();
+		let (path, weights) = astar_path_with_weights(0, &nodes, 15).unwrap();
+		assert_eq!(astar_path(0, &nodes, 15), Some(path.clone()));
+		assert_eq!(path.len(), weights.len());
+		assert_eq!(vec![1.0, 1.0, 1.0, 1.0, 4.0, 3.0, 4.0], weights);
+		for (node, weight) in path.iter().zip(weights.iter()) {
+			assert_eq!(nodes[node].1, *weight);
+		}
+		assert_eq!(None, astar_path_with_weights(0, &no_path_nodes(), 5));
+	}
+	#[test]
+	/// Grid labels mapped to indices in reverse order give an index path which translates
+	/// back to the label path
+	fn indices_round_trip_to_labels() {
+		let nodes = grid_nodes();
+		let index_of: HashMap<i32, u32> = (0..16).map(|node| (node, 15 - node as u32)).collect();
+		let label_of: HashMap<u32, i32> = index_of.iter().map(|(l, i)| (*i, *l)).collect();
+		let indices = astar_path_as_indices(0, &nodes, 15, &index_of).unwrap();
+		assert_eq!(vec![15, 11, 7, 6, 5, 4, 0], indices);
+		let labels: Vec<i32> = indices.iter().map(|index| label_of[index]).collect();
+		assert_eq!(astar_path(0, &nodes, 15), Some(labels));
+	}
+	#[test]
+	#[should_panic]
+	/// A path node without an index can't be translated
+	fn indices_missing_entry() {
+		let nodes = grid_nodes();
+		let index_of: HashMap<i32, u32> = (0..8).map(|node| (node, node as u32)).collect();
+		let _ = astar_path_as_indices(0, &nodes, 15, &index_of);
+	}
+	#[test]
+	/// A bound equal to the optimal distance across the grid still finds the optimal path,
+	/// a bound just short of it finds nothing
+	fn bound_tight_and_too_tight() {
+		let nodes = grid_nodes();
+		assert_eq!(
+			astar_path(0, &nodes, 15),
+			astar_path_with_bound(0, &nodes, 15, 6.0)
+		);
+		assert_eq!(
+			astar_path(0, &nodes, 15),
+			astar_path_with_bound(0, &nodes, 15, f32::INFINITY)
+		);
+		assert_eq!(None, astar_path_with_bound(0, &nodes, 15, 5.9));
+		assert_eq!(Some(vec![0]), astar_path_with_bound(0, &nodes, 0, 0.0));
+	}
+	#[test]
+	/// A shortcut across the grid whose distance is unreliable is taken when only the mean
+	/// matters and avoided under high risk aversion
+	#[allow(clippy::type_complexity)]
+	fn risk_averse_avoids_unreliable_shortcut() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32, f32)>, f32)> = grid_nodes()
+			.into_iter()
+			.map(|(node, (neighbours, weight))| {
+				let neighbours = neighbours.into_iter().map(|(n, d)| (n, d, 0.0)).collect();
+				(node, (neighbours, weight))
+			})
+			.collect();
+		// a shortcut from the start straight to the goal, shorter on average but variable
+		nodes.get_mut(&0).unwrap().0.push((15, 5.0, 9.0));
+		assert_eq!(
+			Some(vec![0, 15]),
+			astar_path_risk_averse(0, &nodes, 15, 0.0)
+		);
+		assert_eq!(
+			Some(vec![0, 15]),
+			astar_path_risk_averse(0, &nodes, 15, 0.1)
+		);
+		let path = astar_path_risk_averse(0, &nodes, 15, 2.0);
+		assert_eq!(astar_path(0, &grid_nodes(), 15), path);
+	}
+	#[test]
+	/// A frontier capped at two routes still finds a path across the grid while never
+	/// holding more than two routes
+	fn capped_frontier_grid() {
+		let nodes = grid_nodes();
+		let (path, stats) = astar_path_capped_frontier_with_stats(0, &nodes, 15, 2);
+		let path = path.unwrap();
+		assert_eq!(Some(&0), path.first());
+		assert_eq!(Some(&15), path.last());
+		assert!(stats.max_frontier <= 2);
+		// a cap larger than the graph behaves as the uncapped search
+		assert_eq!(
+			astar_path(0, &nodes, 15),
+			astar_path_capped_frontier(0, &nodes, 15, 16)
+		);
+	}
 }
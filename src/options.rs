@@ -0,0 +1,204 @@
+//! Per-call configuration of a search gathered into a single struct, in place of a
+//! dedicated function for each combination of settings
+//!
+
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::Debug,
+	hash::Hash,
+	time::{Duration, Instant},
+};
+
+use crate::{
+	a_star_score, check_endpoints, search_by, stored_neighbours, stored_weight, NoListener,
+	PathResult, SearchStats, TieBreak, DEFAULT_SCORE_EPSILON, DEFAULT_TIMEOUT_CHECK_INTERVAL,
+};
+
+/// Settings which tune a search made with [astar_path_with_options], the `Default`
+/// behaves the same as [astar_path](crate::astar_path).
+///
+/// ```rust
+/// use pathfinding_astar::AstarOptions;
+///
+/// let options = AstarOptions {
+///     max_expansions: Some(100),
+///     ..AstarOptions::<i32>::default()
+/// };
+/// assert!(options.blocked.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct AstarOptions<T> {
+	/// Tolerance within which two A-Star scores are considered equal, as per
+	/// [astar_path_with_epsilon](crate::astar_path_with_epsilon)
+	pub epsilon: f32,
+	/// Nodes which can't be entered, as though every edge leading into them was removed.
+	/// The start node may be blocked and will still be left, a blocked end node can't be
+	/// reached
+	pub blocked: HashSet<T>,
+	/// Abandons the search with [PathResult::ExpansionLimit] once this many nodes have
+	/// been expanded
+	pub max_expansions: Option<usize>,
+	/// Abandons the search with [PathResult::Timeout] once this much time has elapsed,
+	/// checked every [DEFAULT_TIMEOUT_CHECK_INTERVAL] expansions
+	pub timeout: Option<Duration>,
+	/// Holds at most this many routes awaiting processing, as per
+	/// [astar_path_capped_frontier](crate::astar_path_capped_frontier)
+	pub max_frontier: Option<usize>,
+	/// Scores the end node as though its weight were `0.0`, so arriving at the goal isn't
+	/// penalised. As every route to the goal shares its weight the best path is unchanged in
+	/// cost, but a route to the goal then reaches the head of the queue sooner, which can
+	/// settle a tie between approaches of equal cost on whichever was discovered first
+	/// rather than last
+	pub exclude_goal_weight: bool,
+}
+
+impl<T> Default for AstarOptions<T> {
+	fn default() -> Self {
+		AstarOptions {
+			epsilon: DEFAULT_SCORE_EPSILON,
+			blocked: HashSet::new(),
+			max_expansions: None,
+			timeout: None,
+			max_frontier: None,
+			exclude_goal_weight: false,
+		}
+	}
+}
+
+/// Behaves like [astar_path](crate::astar_path) with the search tuned by `options`,
+/// returning a [PathResult] to report when a limit set by `options` was reached.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path_with_options, AstarOptions, PathResult};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 5.0)], 1.0));
+/// nodes.insert(1, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(2, (vec![(3, 1.0)], 1.0));
+/// nodes.insert(3, (vec![], 1.0));
+/// let mut options = AstarOptions::default();
+/// options.blocked.insert(1);
+/// let result = astar_path_with_options(0, &nodes, 3, &options);
+/// assert_eq!(PathResult::Found(vec![0, 2, 3]), result);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_with_options<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	options: &AstarOptions<T>,
+) -> PathResult<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let neighbours = stored_neighbours(nodes);
+	let started = Instant::now();
+	search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| Ok(neighbours(node)?.filter(|(neighbour, _)| !options.blocked.contains(neighbour))),
+		stored_weight(nodes, |node, weight| {
+			if options.exclude_goal_weight && *node == end_node {
+				0.0
+			} else {
+				weight
+			}
+		}),
+		options.epsilon,
+		|expansions| {
+			if let Some(max_expansions) = options.max_expansions {
+				if expansions >= max_expansions {
+					return Some(PathResult::ExpansionLimit);
+				}
+			}
+			if let Some(timeout) = options.timeout {
+				if expansions % DEFAULT_TIMEOUT_CHECK_INTERVAL == 0 && started.elapsed() >= timeout
+				{
+					return Some(PathResult::Timeout);
+				}
+			}
+			None
+		},
+		&mut SearchStats::default(),
+		&mut NoListener,
+		|_| (),
+		options.max_frontier.map_or(usize::MAX, |cap| cap.max(1)),
+		f32::INFINITY,
+		a_star_score,
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{astar_path, fixtures::grid_nodes};
+
+	#[test]
+	/// The default options give the same path as [astar_path]
+	fn default_matches_astar_path() {
+		let nodes = grid_nodes();
+		let result = astar_path_with_options(0, &nodes, 15, &AstarOptions::default());
+		assert_eq!(astar_path(0, &nodes, 15), result.into_option());
+	}
+	#[test]
+	/// Blocking the grid path's corner with a wider epsilon detours around it, while a
+	/// low expansion limit stops the same search before it completes
+	fn combined_options() {
+		let nodes = grid_nodes();
+		let mut blocked = HashSet::new();
+		blocked.insert(8);
+		let mut options = AstarOptions {
+			epsilon: 0.5,
+			blocked,
+			max_expansions: Some(100),
+			..AstarOptions::default()
+		};
+		let path = astar_path_with_options(0, &nodes, 15, &options)
+			.into_option()
+			.unwrap();
+		assert!(!path.contains(&8));
+		assert_eq!(Some(&15), path.last());
+		options.max_expansions = Some(2);
+		assert_eq!(
+			PathResult::ExpansionLimit,
+			astar_path_with_options(0, &nodes, 15, &options)
+		);
+		options.max_expansions = None;
+		options.blocked.insert(15);
+		assert_eq!(
+			PathResult::NoPath,
+			astar_path_with_options(0, &nodes, 15, &options)
+		);
+	}
+	#[test]
+	/// Two approaches to the goal travel the same distance, with the goal's weight counted the
+	/// route through `1` reaches the goal first but is replaced once the tied route through
+	/// `2` is found, without the weight the goal is reached before `2` is expanded
+	fn exclude_goal_weight_settles_tie() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (2, 1.0)], 0.0));
+		nodes.insert(1, (vec![(3, 1.0)], 0.0));
+		nodes.insert(2, (vec![(3, 1.0)], 2.0));
+		nodes.insert(3, (vec![], 5.0));
+		let mut options = AstarOptions::default();
+		assert_eq!(
+			PathResult::Found(vec![0, 2, 3]),
+			astar_path_with_options(0, &nodes, 3, &options)
+		);
+		options.exclude_goal_weight = true;
+		assert_eq!(
+			PathResult::Found(vec![0, 1, 3]),
+			astar_path_with_options(0, &nodes, 3, &options)
+		);
+	}
+}
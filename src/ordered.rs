@@ -0,0 +1,96 @@
+//! Searches over graphs held in an insertion-ordered `IndexMap`, for reproducible results
+//! with labels which are hashable but have no ordering
+//!
+
+use std::{fmt::Debug, hash::Hash};
+
+use indexmap::IndexMap;
+
+use crate::equivalence::path_by_index;
+
+/// Behaves like [astar_path](crate::astar_path) for a `nodes` data set held in an
+/// `IndexMap`, available with the `indexmap` feature.
+///
+/// The search runs over the position of each node in `nodes` rather than its label, so
+/// everything it tracks is keyed and ordered by insertion order alone. Routes are still
+/// explored as with [astar_path](crate::astar_path), neighbours in the order they are
+/// listed and tied routes in the order they were discovered, so a graph built in the same
+/// order gives the same path on every run and every machine, without labels needing to be
+/// `Ord` or `Copy`.
+///
+/// ```rust
+/// use indexmap::IndexMap;
+/// use pathfinding_astar::astar_path_indexmap;
+///
+/// let mut nodes: IndexMap<&str, (Vec<(&str, f32)>, f32)> = IndexMap::new();
+/// nodes.insert("home", (vec![("park", 5.0)], 3.0));
+/// nodes.insert("park", (vec![("home", 5.0), ("shop", 4.0)], 2.0));
+/// nodes.insert("shop", (vec![("park", 4.0)], 6.0));
+/// let path = astar_path_indexmap("home", &nodes, "shop");
+/// assert_eq!(Some(vec!["home", "park", "shop"]), path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_indexmap<T>(
+	start_node: T,
+	nodes: &IndexMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+) -> Option<Vec<T>>
+where
+	T: Eq + Hash + Debug + Clone,
+{
+	path_by_index(
+		start_node,
+		end_node,
+		|label| nodes.get_index_of(label),
+		|index| match nodes.get_index(index) {
+			Some(x) => x,
+			None => unreachable!("positions are only taken from `nodes`"),
+		},
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// A room label which can be hashed and compared but not ordered
+	#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+	struct Room(String);
+
+	#[test]
+	/// A square of four rooms offers two routes of identical cost from one corner to the
+	/// opposite one. The route through the room listed last is taken on every run, as it
+	/// ties with and so replaces the route already queued, and swapping the listing swaps
+	/// the route
+	fn ties_break_by_insertion_order() {
+		let room = |name: &str| Room(name.to_string());
+		let build = |first: &str, second: &str| {
+			let mut nodes: IndexMap<Room, (Vec<(Room, f32)>, f32)> = IndexMap::new();
+			nodes.insert(
+				room("hall"),
+				(vec![(room(first), 1.0), (room(second), 1.0)], 1.0),
+			);
+			nodes.insert(room("kitchen"), (vec![(room("attic"), 1.0)], 1.0));
+			nodes.insert(room("study"), (vec![(room("attic"), 1.0)], 1.0));
+			nodes.insert(room("attic"), (vec![], 1.0));
+			nodes
+		};
+		let nodes = build("kitchen", "study");
+		let expected = vec![room("hall"), room("study"), room("attic")];
+		for _ in 0..20 {
+			let path = astar_path_indexmap(room("hall"), &nodes, room("attic"));
+			assert_eq!(Some(expected.clone()), path);
+		}
+		let nodes = build("study", "kitchen");
+		let path = astar_path_indexmap(room("hall"), &nodes, room("attic"));
+		assert_eq!(
+			Some(vec![room("hall"), room("kitchen"), room("attic")]),
+			path
+		);
+	}
+}
@@ -0,0 +1,270 @@
+//! Recording the exact sequence of steps a search carries out so it can be saved alongside
+//! a bug report and replayed later to check the search still behaves identically
+//!
+
+use std::{collections::HashMap, fmt, hash::Hash, str::FromStr};
+
+use crate::{astar_path_with_listener, SearchListener};
+
+/// A single step of a search as recorded in a [SearchLog]
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchEvent<T> {
+	/// `node` was taken from the head of the queue, reached by a route with the given
+	/// A-Star score and distance traveled
+	Expand {
+		/// The node expanded
+		node: T,
+		/// A-Star score of the route which reached it
+		score: f32,
+		/// Distance traveled along the route which reached it
+		distance: f32,
+	},
+	/// A route from `from` to `to` improved upon any found so far and was queued
+	Relax {
+		/// The node being expanded
+		from: T,
+		/// The neighbour reached
+		to: T,
+		/// A-Star score of the new route
+		score: f32,
+	},
+	/// The search completed with the path found, if any
+	Finish(Option<Vec<T>>),
+}
+
+/// The sequence of expansions, queued routes and the final path of a search from
+/// `start_node` to `end_node`, as carried out by [astar_path](crate::astar_path).
+///
+/// As the order of expansion captures every tie-break the search made, replaying the log
+/// against the same `nodes` on another machine or version of the crate confirms whether
+/// the search behaves identically. The log can be written out as text with its `Display`
+/// implementation, one step per line, and read back with [SearchLog::parse] provided node
+/// labels display without whitespace. Scores are written in full so the text round-trips
+/// exactly.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::SearchLog;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// let log = SearchLog::record(0, &nodes, 2);
+/// assert_eq!(Some(&[0, 1, 2][..]), log.path());
+/// let text = log.to_string();
+/// let saved: SearchLog<i32> = SearchLog::parse(&text).unwrap();
+/// assert_eq!(Ok(()), saved.replay(&nodes));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchLog<T> {
+	/// Node the search began from
+	start_node: T,
+	/// Node the search was looking for
+	end_node: T,
+	/// Each step of the search in the order it was carried out
+	events: Vec<SearchEvent<T>>,
+}
+
+impl<T> SearchLog<T>
+where
+	T: Eq + Hash + fmt::Debug + Clone + Copy,
+{
+	/// Searches `nodes` as per [astar_path](crate::astar_path), recording every step taken
+	///
+	/// # Panics
+	///
+	/// Under the same conditions as [astar_path](crate::astar_path)
+	#[allow(clippy::type_complexity)]
+	pub fn record(start_node: T, nodes: &HashMap<T, (Vec<(T, f32)>, f32)>, end_node: T) -> Self {
+		let mut log = SearchLog {
+			start_node,
+			end_node,
+			events: Vec::new(),
+		};
+		let _ = astar_path_with_listener(start_node, nodes, end_node, &mut log);
+		log
+	}
+	/// Every step recorded, in the order it was carried out
+	pub fn events(&self) -> &[SearchEvent<T>] {
+		&self.events
+	}
+	/// The path the recorded search found, `None` if there was no path
+	pub fn path(&self) -> Option<&[T]> {
+		match self.events.last() {
+			Some(SearchEvent::Finish(Some(path))) => Some(path),
+			_ => None,
+		}
+	}
+	/// Searches `nodes` again with the recorded start and end nodes and compares each step
+	/// against the log. On a mismatch the index of the first step which differs is returned,
+	/// which is the length of the shorter log if one is a prefix of the other
+	///
+	/// # Panics
+	///
+	/// Under the same conditions as [astar_path](crate::astar_path)
+	#[allow(clippy::type_complexity)]
+	pub fn replay(&self, nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> Result<(), usize> {
+		let replayed = SearchLog::record(self.start_node, nodes, self.end_node);
+		match self
+			.events
+			.iter()
+			.zip(replayed.events.iter())
+			.position(|(recorded, replayed)| recorded != replayed)
+		{
+			Some(index) => Err(index),
+			None if self.events.len() != replayed.events.len() => {
+				Err(self.events.len().min(replayed.events.len()))
+			}
+			None => Ok(()),
+		}
+	}
+}
+
+impl<T: FromStr> SearchLog<T> {
+	/// Reads back a log written out by its `Display` implementation, `None` if `text` is
+	/// malformed
+	pub fn parse(text: &str) -> Option<Self> {
+		let mut lines = text.lines();
+		let start_node = parse_line(lines.next()?, "start")?;
+		let end_node = parse_line(lines.next()?, "end")?;
+		let mut events = Vec::new();
+		for line in lines {
+			let mut words = line.split_whitespace();
+			let event = match words.next()? {
+				"expand" => SearchEvent::Expand {
+					node: words.next()?.parse().ok()?,
+					score: words.next()?.parse().ok()?,
+					distance: words.next()?.parse().ok()?,
+				},
+				"relax" => SearchEvent::Relax {
+					from: words.next()?.parse().ok()?,
+					to: words.next()?.parse().ok()?,
+					score: words.next()?.parse().ok()?,
+				},
+				"finish" => match words.next() {
+					Some("none") => SearchEvent::Finish(None),
+					first => {
+						let path: Option<Vec<T>> = first
+							.into_iter()
+							.chain(words.by_ref())
+							.map(|w| w.parse().ok())
+							.collect();
+						SearchEvent::Finish(Some(path?))
+					}
+				},
+				_ => return None,
+			};
+			if words.next().is_some() {
+				return None;
+			}
+			events.push(event);
+		}
+		Some(SearchLog {
+			start_node,
+			end_node,
+			events,
+		})
+	}
+}
+
+/// Parses the value of a `keyword value` line of a written log
+fn parse_line<T: FromStr>(line: &str, keyword: &str) -> Option<T> {
+	let mut words = line.split_whitespace();
+	if words.next()? != keyword {
+		return None;
+	}
+	let value = words.next()?.parse().ok()?;
+	match words.next() {
+		Some(_) => None,
+		None => Some(value),
+	}
+}
+
+impl<T: fmt::Display> fmt::Display for SearchLog<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "start {}", self.start_node)?;
+		writeln!(f, "end {}", self.end_node)?;
+		for event in self.events.iter() {
+			match event {
+				SearchEvent::Expand {
+					node,
+					score,
+					distance,
+				} => writeln!(f, "expand {} {} {}", node, score, distance)?,
+				SearchEvent::Relax { from, to, score } => {
+					writeln!(f, "relax {} {} {}", from, to, score)?
+				}
+				SearchEvent::Finish(None) => writeln!(f, "finish none")?,
+				SearchEvent::Finish(Some(path)) => {
+					write!(f, "finish")?;
+					for node in path.iter() {
+						write!(f, " {}", node)?;
+					}
+					writeln!(f)?;
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<T: Clone> SearchListener<T> for SearchLog<T> {
+	fn on_expand(&mut self, node: &T, score: f32, distance: f32) {
+		self.events.push(SearchEvent::Expand {
+			node: node.clone(),
+			score,
+			distance,
+		});
+	}
+	fn on_relax(&mut self, from: &T, to: &T, new_score: f32) {
+		self.events.push(SearchEvent::Relax {
+			from: from.clone(),
+			to: to.clone(),
+			score: new_score,
+		});
+	}
+	fn on_finish(&mut self, path: &Option<Vec<T>>) {
+		self.events.push(SearchEvent::Finish(path.clone()));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		astar_path,
+		fixtures::{grid_nodes, no_path_nodes},
+	};
+
+	#[test]
+	/// A log of the grid search survives being written out and read back, and replays
+	/// exactly against the same grid but not once a weight changes
+	fn grid_log_replays_exactly() {
+		let mut nodes = grid_nodes();
+		let log = SearchLog::record(0, &nodes, 15);
+		assert_eq!(astar_path(0, &nodes, 15).as_deref(), log.path());
+		assert!(matches!(
+			log.events()[0],
+			SearchEvent::Expand { node: 0, .. }
+		));
+		let saved: SearchLog<i32> = SearchLog::parse(&log.to_string()).unwrap();
+		assert_eq!(log, saved);
+		assert_eq!(Ok(()), saved.replay(&nodes));
+		nodes.get_mut(&9).unwrap().1 = 2.0;
+		assert!(saved.replay(&nodes).is_err());
+	}
+	#[test]
+	/// A search which finds no path round-trips, malformed text is rejected
+	fn no_path_log_and_malformed_text() {
+		let nodes = no_path_nodes();
+		let log = SearchLog::record(0, &nodes, 5);
+		assert_eq!(None, log.path());
+		assert_eq!(Some(log.clone()), SearchLog::parse(&log.to_string()));
+		assert_eq!(
+			None,
+			SearchLog::<i32>::parse("start 0\nend 5\nexpand 0 zero 0")
+		);
+		assert_eq!(None, SearchLog::<i32>::parse("end 5\nstart 0"));
+	}
+}
@@ -0,0 +1,202 @@
+//! Lazily discovered graphs whose geometry streams in and out while searches are running,
+//! such as the chunks of an open world loading around a moving agent
+//!
+
+use std::{cell::RefCell, collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::{
+	a_star_score, search_by, PathResult, SearchListener, SearchStats, TieBreak,
+	DEFAULT_SCORE_EPSILON,
+};
+
+/// Memoizes the neighbours produced by a generator, as used by
+/// [astar_path_lazy](crate::astar_path_lazy), so that repeated searches over the same area
+/// don't generate them again.
+///
+/// When a region of the world unloads or changes, [NeighbourCache::invalidate] drops the
+/// cached neighbours of the nodes within it and the next query regenerates them. The cache
+/// is borrowed immutably throughout so it may be invalidated while a search is using it,
+/// such as from a [SearchListener].
+///
+/// ```rust
+/// use pathfinding_astar::NeighbourCache;
+///
+/// let cache = NeighbourCache::new(|n: &i32| vec![(n - 1, 1.0), (n + 1, 1.0)]);
+/// assert_eq!(vec![(4, 1.0), (6, 1.0)], cache.neighbours(&5));
+/// assert!(cache.is_cached(&5));
+/// cache.invalidate(|n| (0..10).contains(n));
+/// assert!(!cache.is_cached(&5));
+/// ```
+pub struct NeighbourCache<T, N> {
+	/// Produces the `(neighbour_label, distance_to_neighbour)` pairs of a node
+	generate: N,
+	/// Neighbours generated so far, keyed by the node they belong to
+	cached: RefCell<HashMap<T, Vec<(T, f32)>>>,
+}
+
+impl<T, N, NI> NeighbourCache<T, N>
+where
+	T: Eq + Hash + Clone + Copy,
+	N: Fn(&T) -> NI,
+	NI: IntoIterator<Item = (T, f32)>,
+{
+	/// An empty cache generating neighbours with `generate`
+	pub fn new(generate: N) -> Self {
+		NeighbourCache {
+			generate,
+			cached: RefCell::new(HashMap::new()),
+		}
+	}
+	/// The neighbours of `node`, generated and cached if they aren't already
+	pub fn neighbours(&self, node: &T) -> Vec<(T, f32)> {
+		if let Some(neighbours) = self.cached.borrow().get(node) {
+			return neighbours.clone();
+		}
+		let neighbours: Vec<(T, f32)> = (self.generate)(node).into_iter().collect();
+		self.cached.borrow_mut().insert(*node, neighbours.clone());
+		neighbours
+	}
+	/// Whether the neighbours of `node` are currently cached
+	pub fn is_cached(&self, node: &T) -> bool {
+		self.cached.borrow().contains_key(node)
+	}
+	/// Number of nodes whose neighbours are currently cached
+	pub fn len(&self) -> usize {
+		self.cached.borrow().len()
+	}
+	/// Whether no neighbours are currently cached
+	pub fn is_empty(&self) -> bool {
+		self.cached.borrow().is_empty()
+	}
+	/// Drops the cached neighbours of every node for which `region` returns `true`, they are
+	/// generated afresh when next queried
+	pub fn invalidate<R: Fn(&T) -> bool>(&self, region: R) {
+		self.cached.borrow_mut().retain(|node, _| !region(node));
+	}
+}
+
+/// Behaves like [astar_path_lazy](crate::astar_path_lazy) but draws neighbours from
+/// `cache`, generating only those not already cached, and notifies `listener` as the
+/// search progresses.
+///
+/// Invalidating part of `cache` while the search runs, such as from `listener` as a chunk
+/// unloads, causes any node of the region expanded afterwards to have its neighbours
+/// generated again, so the search follows the geometry as it streams.
+///
+/// ```rust
+/// use pathfinding_astar::{astar_path_streaming, NeighbourCache, PathResult};
+///
+/// let cache = NeighbourCache::new(|n: &i32| vec![(n - 1, 1.0), (n + 1, 1.0)]);
+/// let end = 3;
+/// let weight = |n: &i32| (end - n).abs() as f32;
+/// let result = astar_path_streaming(0, end, &cache, weight, 100, &mut ());
+/// assert_eq!(PathResult::Found(vec![0, 1, 2, 3]), result);
+/// assert!(cache.is_cached(&2));
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path_lazy](crate::astar_path_lazy)
+#[must_use = "the computed path is discarded if unused"]
+pub fn astar_path_streaming<T, N, NI, W, L>(
+	start_node: T,
+	end_node: T,
+	cache: &NeighbourCache<T, N>,
+	weight_fn: W,
+	max_expansions: usize,
+	listener: &mut L,
+) -> PathResult<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+	N: Fn(&T) -> NI,
+	NI: IntoIterator<Item = (T, f32)>,
+	W: Fn(&T) -> f32,
+	L: SearchListener<T>,
+{
+	let result = search_by(
+		start_node,
+		|node, _, _| *node == end_node,
+		|node| Ok(cache.neighbours(node)),
+		|node| {
+			let weight = weight_fn(node);
+			Ok((weight, weight))
+		},
+		DEFAULT_SCORE_EPSILON,
+		|expansions| {
+			if expansions >= max_expansions {
+				Some(PathResult::ExpansionLimit)
+			} else {
+				None
+			}
+		},
+		&mut SearchStats::default(),
+		listener,
+		|_| (),
+		usize::MAX,
+		f32::INFINITY,
+		a_star_score,
+		TieBreak::DistanceTraveled,
+	)
+	.unwrap_or_else(|e| panic!("{}", e));
+	listener.on_finish(&result.clone().into_option());
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::cell::Cell;
+
+	/// Unloads the chunk of nodes `5..10` once the search expands node `3`
+	struct Unloader<'a, N> {
+		/// The cache shared with the search
+		cache: &'a NeighbourCache<i32, N>,
+		/// Set once the chunk has been unloaded
+		unloaded: &'a Cell<bool>,
+	}
+
+	impl<'a, N, NI> SearchListener<i32> for Unloader<'a, N>
+	where
+		N: Fn(&i32) -> NI,
+		NI: IntoIterator<Item = (i32, f32)>,
+	{
+		fn on_expand(&mut self, node: &i32, _score: f32, _distance: f32) {
+			if *node == 3 && !self.unloaded.get() {
+				self.unloaded.set(true);
+				self.cache.invalidate(|n| (5..10).contains(n));
+			}
+		}
+	}
+
+	#[test]
+	/// A chunk invalidated part way through a search is generated again when reached, and
+	/// the search follows its new geometry: a bridge from `6` to the goal `20` which only
+	/// exists once the chunk has reloaded
+	fn invalidated_chunk_is_regenerated() {
+		let unloaded = Cell::new(false);
+		let generated: RefCell<Vec<i32>> = RefCell::new(Vec::new());
+		let cache = NeighbourCache::new(|n: &i32| {
+			generated.borrow_mut().push(*n);
+			if *n == 6 && unloaded.get() {
+				vec![(20, 1.0), (n + 1, 1.0)]
+			} else {
+				vec![(n + 1, 1.0)]
+			}
+		});
+		// warm the cache along the line as a previous search would have
+		for n in 0..10 {
+			let _ = cache.neighbours(&n);
+		}
+		assert_eq!(10, cache.len());
+		generated.borrow_mut().clear();
+		let mut listener = Unloader {
+			cache: &cache,
+			unloaded: &unloaded,
+		};
+		let result = astar_path_streaming(0, 20, &cache, |_| 0.0, 100, &mut listener);
+		assert_eq!(PathResult::Found(vec![0, 1, 2, 3, 4, 5, 6, 20]), result);
+		// only the reloaded chunk was generated again
+		assert_eq!(vec![5, 6], *generated.borrow());
+		assert!(!cache.is_cached(&7));
+	}
+}
@@ -0,0 +1,382 @@
+//! Diagnostics for catching modelling mistakes in a `nodes` data set before searching it
+//!
+
+use std::{
+	collections::{HashMap, HashSet},
+	hash::Hash,
+};
+
+use crate::{graph::edges, DEFAULT_SCORE_EPSILON};
+
+/// A problem found in a `nodes` data set by [validate_graph]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphIssue<T> {
+	/// A group of nodes, each with a weight of `0.0`, which can be travelled around
+	/// indefinitely using edges with a distance of `0.0`. Routes around the cycle all share
+	/// the same score which causes the search to churn through equal-score entries. The
+	/// nodes are given in no particular order
+	ZeroCostCycle(Vec<T>),
+	/// An edge `(from, to)` leads to a node which isn't a key of the data set, typically a
+	/// sink which was given no entry of its own. A search which discovers the edge fails with
+	/// [AstarError::MissingNode](crate::AstarError::MissingNode)
+	DanglingEdge(T, T),
+}
+
+/// Inspects `nodes` for modelling mistakes which either cause a search to fail part way
+/// through or are likely to cause surprising behaviour. An empty list means no issues
+/// were found.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{validate_graph, GraphIssue};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 1.0));
+/// nodes.insert(1, (vec![(0, 1.0)], 1.0));
+/// assert!(validate_graph(&nodes).is_empty());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn validate_graph<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> Vec<GraphIssue<T>>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	let mut issues: Vec<GraphIssue<T>> = Vec::new();
+	for (from, to, _) in edges(nodes) {
+		if !nodes.contains_key(&to) {
+			issues.push(GraphIssue::DanglingEdge(from, to));
+		}
+	}
+	issues.extend(
+		zero_cost_cycles(nodes)
+			.into_iter()
+			.map(GraphIssue::ZeroCostCycle),
+	);
+	issues
+}
+
+/// Finds the edges `(a, b)` of `nodes` along which the heuristic `h` is inconsistent
+/// towards `end_node`, where `h(a, end_node)` exceeds the distance from `a` to `b` plus
+/// `h(b, end_node)`. An empty list means `h` is consistent.
+///
+/// A search whose node weights are estimates of the distance remaining to `end_node` only
+/// returns the most optimal path if those estimates are consistent, so this is a debugging
+/// aid for heuristics used to derive weights, such as the manhattan distance of a grid.
+/// Differences within [DEFAULT_SCORE_EPSILON] are ignored as floating point noise.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::check_heuristic_consistency;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 0.0));
+/// nodes.insert(1, (vec![(2, 1.0)], 0.0));
+/// nodes.insert(2, (vec![], 0.0));
+/// let remaining = |node: &i32, end: &i32| (end - node) as f32;
+/// assert!(check_heuristic_consistency(&nodes, remaining, 2).is_empty());
+/// let overestimate = |node: &i32, end: &i32| if *node == 0 { 5.0 } else { (end - node) as f32 };
+/// assert_eq!(vec![(0, 1)], check_heuristic_consistency(&nodes, overestimate, 2));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn check_heuristic_consistency<T, H>(
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	h: H,
+	end_node: T,
+) -> Vec<(T, T)>
+where
+	T: Eq + Hash + Clone + Copy,
+	H: Fn(&T, &T) -> f32,
+{
+	edges(nodes)
+		.filter(|(a, b, distance)| {
+			h(a, &end_node) > distance + h(b, &end_node) + DEFAULT_SCORE_EPSILON
+		})
+		.map(|(a, b, _)| (a, b))
+		.collect()
+}
+
+/// Finds every node which is fully disconnected, having no neighbours of its own and not
+/// being the neighbour of any other node, such as an orphan tile of a procedurally
+/// generated map. The nodes are given in no particular order.
+///
+/// A sink, a node with no neighbours which other nodes lead to, is still reachable and so
+/// isn't reported.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::isolated_nodes;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0)], 1.0));
+/// nodes.insert(1, (vec![], 1.0));
+/// nodes.insert(2, (vec![], 1.0));
+/// assert_eq!(vec![2], isolated_nodes(&nodes));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn isolated_nodes<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> Vec<T>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	let referenced: HashSet<T> = edges(nodes).map(|(_, to, _)| to).collect();
+	nodes
+		.iter()
+		.filter(|(node, (neighbours, _))| neighbours.is_empty() && !referenced.contains(node))
+		.map(|(node, _)| *node)
+		.collect()
+}
+
+/// Summary figures of a `nodes` data set produced by [graph_stats]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphStats {
+	/// Number of nodes, the keys of the data set
+	pub node_count: usize,
+	/// Number of directional edges, counting each neighbour of each node
+	pub edge_count: usize,
+	/// Shortest edge distance, `None` without any edges
+	pub min_distance: Option<f32>,
+	/// Longest edge distance, `None` without any edges
+	pub max_distance: Option<f32>,
+	/// Mean edge distance, `None` without any edges
+	pub mean_distance: Option<f32>,
+	/// Lowest node weight, `None` without any nodes
+	pub min_weight: Option<f32>,
+	/// Highest node weight, `None` without any nodes
+	pub max_weight: Option<f32>,
+	/// Mean node weight, `None` without any nodes
+	pub mean_weight: Option<f32>,
+	/// Number of sinks, nodes with no neighbours which other nodes lead to
+	pub sink_count: usize,
+	/// Number of sources, nodes with neighbours which no other node leads to
+	pub source_count: usize,
+}
+
+/// Gathers summary figures of `nodes`, a quick health check of the data before searching
+/// it such as spotting an unexpected spread of weights or a graph which is mostly sinks.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::graph_stats;
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 1.0), (2, 3.0)], 2.0));
+/// nodes.insert(1, (vec![(2, 2.0)], 4.0));
+/// nodes.insert(2, (vec![], 6.0));
+/// let stats = graph_stats(&nodes);
+/// assert_eq!(3, stats.edge_count);
+/// assert_eq!(Some(2.0), stats.mean_distance);
+/// assert_eq!(Some(4.0), stats.mean_weight);
+/// assert_eq!((1, 1), (stats.sink_count, stats.source_count));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn graph_stats<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> GraphStats
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	/// The minimum, maximum and mean of `values`, `None` if there are none
+	fn spread<I: Iterator<Item = f32>>(values: I) -> (Option<f32>, Option<f32>, Option<f32>) {
+		let (count, min, max, sum) = values.fold(
+			(0, f32::INFINITY, f32::NEG_INFINITY, 0.0),
+			|(count, min, max, sum), value| {
+				(count + 1, min.min(value), max.max(value), sum + value)
+			},
+		);
+		if count == 0 {
+			(None, None, None)
+		} else {
+			(Some(min), Some(max), Some(sum / count as f32))
+		}
+	}
+	let referenced: HashSet<T> = edges(nodes).map(|(_, to, _)| to).collect();
+	let (min_distance, max_distance, mean_distance) =
+		spread(edges(nodes).map(|(_, _, distance)| distance));
+	let (min_weight, max_weight, mean_weight) = spread(nodes.values().map(|(_, weight)| *weight));
+	let sink_count = nodes
+		.iter()
+		.filter(|(node, (neighbours, _))| neighbours.is_empty() && referenced.contains(node))
+		.count();
+	let source_count = nodes
+		.iter()
+		.filter(|(node, (neighbours, _))| !neighbours.is_empty() && !referenced.contains(node))
+		.count();
+	GraphStats {
+		node_count: nodes.len(),
+		edge_count: edges(nodes).count(),
+		min_distance,
+		max_distance,
+		mean_distance,
+		min_weight,
+		max_weight,
+		mean_weight,
+		sink_count,
+		source_count,
+	}
+}
+
+/// Finds the strongly connected components of the subgraph made of zero weight nodes
+/// joined by zero distance edges, every component which contains a cycle is returned
+#[allow(clippy::type_complexity)]
+fn zero_cost_cycles<T>(nodes: &HashMap<T, (Vec<(T, f32)>, f32)>) -> Vec<Vec<T>>
+where
+	T: Eq + Hash + Clone + Copy,
+{
+	let is_free = |node: &T| matches!(nodes.get(node), Some((_, w)) if *w == 0.0);
+	// zero cost edges in both directions
+	let mut forward: HashMap<T, Vec<T>> = HashMap::new();
+	let mut reverse: HashMap<T, Vec<T>> = HashMap::new();
+	for (node, (neighbours, _)) in nodes.iter() {
+		if !is_free(node) {
+			continue;
+		}
+		for (neighbour, distance) in neighbours.iter() {
+			if *distance == 0.0 && is_free(neighbour) {
+				forward.entry(*node).or_default().push(*neighbour);
+				reverse.entry(*neighbour).or_default().push(*node);
+			}
+		}
+	}
+	// Kosaraju: order nodes by the completion of a depth first search...
+	let mut visited: HashSet<T> = HashSet::new();
+	let mut finish_order: Vec<T> = Vec::new();
+	for root in forward.keys() {
+		if !visited.insert(*root) {
+			continue;
+		}
+		let mut stack: Vec<(T, usize)> = vec![(*root, 0)];
+		while let Some((node, index)) = stack.pop() {
+			let next = forward.get(&node).and_then(|n| n.get(index)).copied();
+			match next {
+				Some(neighbour) => {
+					stack.push((node, index + 1));
+					if visited.insert(neighbour) {
+						stack.push((neighbour, 0));
+					}
+				}
+				None => finish_order.push(node),
+			}
+		}
+	}
+	// ...then collect components by searching the reversed edges in reverse finish order
+	let mut assigned: HashSet<T> = HashSet::new();
+	let mut cycles = Vec::new();
+	for root in finish_order.iter().rev() {
+		if !assigned.insert(*root) {
+			continue;
+		}
+		let mut component = vec![*root];
+		let mut stack = vec![*root];
+		while let Some(node) = stack.pop() {
+			for neighbour in reverse.get(&node).into_iter().flatten() {
+				if assigned.insert(*neighbour) {
+					component.push(*neighbour);
+					stack.push(*neighbour);
+				}
+			}
+		}
+		let self_loop = forward
+			.get(root)
+			.map_or(false, |neighbours| neighbours.contains(root));
+		if component.len() > 1 || self_loop {
+			cycles.push(component);
+		}
+	}
+	cycles
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fixtures::grid_nodes;
+
+	#[test]
+	/// An orphan node is reported while a sink which other nodes lead to is not
+	fn isolated_but_not_sink() {
+		let mut nodes = grid_nodes();
+		assert!(isolated_nodes(&nodes).is_empty());
+		// a sink, reached from 15 but leading nowhere
+		nodes.get_mut(&15).unwrap().0.push((16, 1.0));
+		nodes.insert(16, (vec![], 1.0));
+		// an orphan
+		nodes.insert(17, (vec![], 1.0));
+		assert_eq!(vec![17], isolated_nodes(&nodes));
+	}
+	#[test]
+	/// The grid has sixteen nodes joined both ways by forty eight unit edges, with weights
+	/// from `1` to `14` and neither sinks nor sources
+	fn grid_graph_stats() {
+		let stats = graph_stats(&grid_nodes());
+		assert_eq!(16, stats.node_count);
+		assert_eq!(48, stats.edge_count);
+		assert_eq!(Some(1.0), stats.min_weight);
+		assert_eq!(Some(14.0), stats.max_weight);
+		assert_eq!(Some(83.0 / 16.0), stats.mean_weight);
+		assert_eq!(Some(1.0), stats.min_distance);
+		assert_eq!(Some(1.0), stats.max_distance);
+		assert_eq!((0, 0), (stats.sink_count, stats.source_count));
+		let empty: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		assert_eq!(None, graph_stats(&empty).mean_weight);
+	}
+	#[test]
+	/// Nodes `1` and `2` bounce between each other for free and are reported
+	fn two_node_zero_cost_cycle() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0), (3, 4.0)], 1.0));
+		nodes.insert(1, (vec![(2, 0.0)], 0.0));
+		nodes.insert(2, (vec![(1, 0.0)], 0.0));
+		nodes.insert(3, (vec![], 1.0));
+		let issues = validate_graph(&nodes);
+		assert_eq!(1, issues.len());
+		let mut cycle = match issues[0].clone() {
+			GraphIssue::ZeroCostCycle(cycle) => cycle,
+			issue => panic!("Expected a zero cost cycle, found {:?}", issue),
+		};
+		cycle.sort();
+		assert_eq!(vec![1, 2], cycle);
+	}
+	#[test]
+	/// A node with a zero distance edge to itself is a cycle
+	fn zero_cost_self_loop() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(0, 0.0)], 0.0));
+		assert_eq!(
+			vec![GraphIssue::ZeroCostCycle(vec![0])],
+			validate_graph(&nodes)
+		);
+	}
+	#[test]
+	/// Zero distances alone are fine if the nodes carry weight, as is a zero weight chain
+	fn no_zero_cost_cycles() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 0.0)], 1.0));
+		nodes.insert(1, (vec![(0, 0.0), (2, 0.0)], 0.0));
+		nodes.insert(2, (vec![], 0.0));
+		assert!(validate_graph(&nodes).is_empty());
+		assert!(validate_graph(&grid_nodes()).is_empty());
+	}
+	#[test]
+	/// A sink referenced by an edge but never given an entry is reported before searching
+	fn dangling_edge_to_sink() {
+		let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+		nodes.insert(0, (vec![(1, 1.0)], 1.0));
+		nodes.insert(1, (vec![(2, 1.0)], 1.0));
+		assert_eq!(vec![GraphIssue::DanglingEdge(1, 2)], validate_graph(&nodes));
+	}
+	#[test]
+	/// Manhattan distance across the grid is consistent, overestimating at node `14` breaks
+	/// consistency along each of its edges
+	fn grid_heuristic_consistency() {
+		let nodes = grid_nodes();
+		let manhattan = |node: &i32, end: &i32| {
+			((node % 4 - end % 4).abs() + (node / 4 - end / 4).abs()) as f32
+		};
+		assert!(check_heuristic_consistency(&nodes, manhattan, 15).is_empty());
+		let inconsistent = |node: &i32, end: &i32| {
+			if *node == 14 {
+				10.0
+			} else {
+				manhattan(node, end)
+			}
+		};
+		let mut violations = check_heuristic_consistency(&nodes, inconsistent, 15);
+		violations.sort();
+		assert_eq!(vec![(14, 10), (14, 13), (14, 15)], violations);
+	}
+}
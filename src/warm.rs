@@ -0,0 +1,195 @@
+//! Replanning by resuming an earlier search rather than starting over
+//!
+
+use std::{cell::RefCell, collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::{
+	a_star_score, check_endpoints, resume_by, stored_neighbours, stored_weight, NoListener, Route,
+	SearchState, SearchStats, TieBreak, DEFAULT_SCORE_EPSILON,
+};
+
+/// The queue, best scores and expanded nodes left by a search made with
+/// [astar_path_warm], which a later search between the same nodes can resume from.
+///
+/// Along with the state of the search it holds a copy of the part of the graph the search
+/// has seen: the neighbours and weight of every expanded node and the weight of every node
+/// discovered. When resumed this is checked against the graph as it stands, if anything
+/// the search relied upon has been edited the state is discarded and the search starts
+/// afresh, otherwise the answer is the same as a search from scratch but only the work
+/// beyond the earlier search is carried out.
+#[allow(clippy::type_complexity)]
+pub struct WarmStart<T> {
+	/// Node the search began from
+	start_node: T,
+	/// Node the search is looking for
+	end_node: T,
+	/// Progress of the search
+	state: SearchState<T, ()>,
+	/// Each node looked up by the search mapped to its neighbours, if it was expanded, and
+	/// its weight, as they were at the time
+	seen: HashMap<T, (Option<Vec<(T, f32)>>, f32)>,
+}
+
+impl<T> WarmStart<T>
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	/// Whether the state was left by a search between `start_node` and `end_node` and every
+	/// node it looked up still has the same weight and, if expanded, neighbours in `nodes`
+	#[allow(clippy::type_complexity)]
+	pub fn is_valid_for(
+		&self,
+		start_node: T,
+		nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+		end_node: T,
+	) -> bool {
+		self.start_node == start_node
+			&& self.end_node == end_node
+			&& self
+				.seen
+				.iter()
+				.all(
+					|(node, (seen_neighbours, seen_weight))| match nodes.get(node) {
+						Some((neighbours, weight)) => {
+							weight == seen_weight
+								&& seen_neighbours
+									.as_ref()
+									.map_or(true, |seen| seen == neighbours)
+						}
+						None => false,
+					},
+				)
+	}
+}
+
+/// Behaves like [astar_path](crate::astar_path) but resumes from `warm_start`, the state
+/// left by an earlier call, when it's still valid for the graph, see [WarmStart]. The state
+/// left by this search is returned alongside the path for the next replan.
+///
+/// Edits to parts of the graph the earlier search never looked at, such as the far side of
+/// the map, keep the state valid. An edit the earlier search relied upon, or a different
+/// start or end node, causes a search from scratch.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use pathfinding_astar::{astar_path, astar_path_warm};
+///
+/// let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+/// nodes.insert(0, (vec![(1, 5.0)], 3.0));
+/// nodes.insert(1, (vec![(0, 5.0), (2, 4.0)], 2.0));
+/// nodes.insert(2, (vec![(1, 4.0)], 6.0));
+/// nodes.insert(3, (vec![(2, 1.0)], 1.0));
+/// let (path, warm) = astar_path_warm(0, &nodes, 2, None);
+/// assert_eq!(Some(vec![0, 1, 2]), path);
+/// // node 3 was never reached so editing it leaves the earlier search valid
+/// nodes.get_mut(&3).unwrap().1 = 9.0;
+/// assert!(warm.is_valid_for(0, &nodes, 2));
+/// let (path, _) = astar_path_warm(0, &nodes, 2, Some(warm));
+/// assert_eq!(astar_path(0, &nodes, 2), path);
+/// ```
+///
+/// # Panics
+///
+/// Under the same conditions as [astar_path](crate::astar_path)
+#[must_use = "the computed path is discarded if unused"]
+#[allow(clippy::type_complexity)]
+pub fn astar_path_warm<T>(
+	start_node: T,
+	nodes: &HashMap<T, (Vec<(T, f32)>, f32)>,
+	end_node: T,
+	warm_start: Option<WarmStart<T>>,
+) -> (Option<Vec<T>>, WarmStart<T>)
+where
+	T: Eq + Hash + Debug + Clone + Copy,
+{
+	// ensure nodes data contains start and end points
+	check_endpoints(nodes, start_node, end_node).unwrap_or_else(|e| panic!("{}", e));
+	let (state, seen) = match warm_start {
+		Some(warm) if warm.is_valid_for(start_node, nodes, end_node) => {
+			(Some(warm.state), warm.seen)
+		}
+		_ => (None, HashMap::new()),
+	};
+	// both lookups record what they see
+	let seen = RefCell::new(seen);
+	let lookup_neighbours = stored_neighbours(nodes);
+	let mut neighbours = |node: &T| {
+		let found = lookup_neighbours(node)?;
+		if let Some(entry) = seen.borrow_mut().get_mut(node) {
+			entry.0 = Some(found.clone().collect());
+		}
+		Ok(found)
+	};
+	let lookup_weight = stored_weight(nodes, |_, weight| weight);
+	let mut weight = |node: &T| {
+		let found = lookup_weight(node)?;
+		seen.borrow_mut().entry(*node).or_insert((None, found.1));
+		Ok(found)
+	};
+	let mut state = match state {
+		Some(state) => state,
+		None => SearchState::new(
+			start_node,
+			&mut weight,
+			&mut |_: &Route<T>| (),
+			TieBreak::DistanceTraveled,
+		)
+		.unwrap_or_else(|e| panic!("{}", e)),
+	};
+	let path = resume_by(
+		&mut state,
+		|node, _, _| *node == end_node,
+		&mut neighbours,
+		&mut weight,
+		DEFAULT_SCORE_EPSILON,
+		|_| None,
+		&mut SearchStats::default(),
+		&mut NoListener,
+		|_| (),
+		usize::MAX,
+		f32::INFINITY,
+		a_star_score,
+	)
+	.unwrap_or_else(|e| panic!("{}", e))
+	.into_option();
+	let warm = WarmStart {
+		start_node,
+		end_node,
+		state,
+		seen: seen.into_inner(),
+	};
+	(path, warm)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{astar_path, astar_path_with_stats, fixtures::large_grid_nodes};
+
+	#[test]
+	/// Editing the far corner of the grid keeps the earlier search valid and resuming from
+	/// it matches a cold search, editing a node it expanded starts over and still matches
+	fn warm_start_matches_cold_search() {
+		let mut nodes = large_grid_nodes(12);
+		let (path, warm) = astar_path_warm(0, &nodes, 27, None);
+		assert_eq!(astar_path(0, &nodes, 27), path);
+		let expanded = warm.state.expanded.len();
+		// the far corner is never looked at
+		assert!(!warm.seen.contains_key(&143));
+		nodes.get_mut(&143).unwrap().1 = 50.0;
+		assert!(warm.is_valid_for(0, &nodes, 27));
+		let (path, warm) = astar_path_warm(0, &nodes, 27, Some(warm));
+		assert_eq!(astar_path(0, &nodes, 27), path);
+		// nothing more needed expanding
+		assert_eq!(expanded, warm.state.expanded.len());
+		// node 1 is expanded on the way, making it costly changes the route
+		nodes.get_mut(&1).unwrap().1 = 50.0;
+		assert!(!warm.is_valid_for(0, &nodes, 27));
+		let (path, warm) = astar_path_warm(0, &nodes, 27, Some(warm));
+		assert_eq!(astar_path(0, &nodes, 27), path);
+		let (_, stats) = astar_path_with_stats(0, &nodes, 27);
+		assert_eq!(stats.expansions, warm.state.expanded.len());
+		// a different goal can't reuse the search
+		assert!(!warm.is_valid_for(0, &nodes, 26));
+	}
+}
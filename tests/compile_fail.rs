@@ -0,0 +1,9 @@
+//! Checks that misuse of the public API is caught at compile time
+//!
+
+#[test]
+/// Discarding a computed path triggers the `unused_must_use` lint
+fn must_use_paths() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/*.rs");
+}
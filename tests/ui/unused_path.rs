@@ -0,0 +1,11 @@
+#![deny(unused_must_use)]
+
+use pathfinding_astar::astar_path;
+use std::collections::HashMap;
+
+fn main() {
+	let mut nodes: HashMap<i32, (Vec<(i32, f32)>, f32)> = HashMap::new();
+	nodes.insert(0, (vec![(1, 5.0)], 3.0));
+	nodes.insert(1, (vec![(0, 5.0)], 2.0));
+	astar_path(0, &nodes, 1);
+}